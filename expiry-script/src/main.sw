@@ -0,0 +1,24 @@
+script;
+
+// ⏰ EXPIRY SCRIPT
+
+use std::block::{height, timestamp};
+
+// Predicates can't read the block height or time, so fills of an expiring order have to
+// run this script (the predicate checks its bytecode hash and that EXPIRY is the script data).
+// Values from 2^62 up are TAI64 timestamps, anything below is a block height.
+
+const TAI64_OFFSET: u64 = 4611686018427387904; // 2^62
+
+enum Errors {
+    OrderExpired: (),
+}
+
+fn main(expiry: u64) {
+    let now = if expiry >= TAI64_OFFSET {
+        timestamp()
+    } else {
+        height().as_u64()
+    };
+    require(now <= expiry, Errors::OrderExpired);
+}
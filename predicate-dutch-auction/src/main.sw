@@ -0,0 +1,149 @@
+predicate;
+
+// 🟠 DUTCH AUCTION PREDICATE
+
+use std::u256::U256;
+use std::auth::predicate_address;
+use std::outputs::{Output, output_asset_id, output_amount, output_count, output_type, output_asset_to};
+use std::inputs::{input_asset_id, input_count, input_coin_owner};
+use std::constants::{ZERO_B256};
+use std::tx::tx_maturity;
+
+// Maker(Alice) sells BTC for USDC, asking START_PRICE at START_BLOCK and down to END_PRICE
+// at END_BLOCK, linearly in between
+// Taker(Bob) pays the price of the block his tx matures at
+
+configurable {
+    QUOTE_ASSET: b256 = ZERO_B256, // Asset that provides taker(Bob)
+    BASE_ASSET: b256 = ZERO_B256, // Asset that provides maker(Alice)
+    MAKER: Address = Address::from(ZERO_B256), // Order owner
+    START_PRICE: u64 = 0,
+    END_PRICE: u64 = 0,
+    START_BLOCK: u32 = 0,
+    END_BLOCK: u32 = 0,
+    QUOTE_DECIMALS: u32 = 9,
+    BASE_DECIMALS: u32 = 9,
+    PRICE_DECIMALS: u32 = 9, // optional
+    NONCE: b256 = ZERO_B256, // optional, only there to give otherwise identical auctions distinct roots
+}
+
+impl u64 {
+    pub fn mul_div(self, mul_to: u64, div_to: u64) -> u64 {
+        let mul_result = U256::from((0, 0, 0, self)) * U256::from((0, 0, 0, mul_to));
+        let div_result = mul_result / U256::from((0, 0, 0, div_to));
+        div_result.as_u64().unwrap()
+    }
+}
+
+fn main() -> bool {
+    assert(END_PRICE > 0 && START_PRICE >= END_PRICE && START_BLOCK < END_BLOCK);
+    assert(MAKER.into() != ZERO_B256);
+
+    let mut i = 0u8;
+    let inputs: u8 = input_count();
+    while i < inputs  {
+        // contract and message inputs have no owner, they can't be the maker's
+        if let Some(owner) = input_coin_owner(i.as_u64()) {
+            if owner == MAKER {
+                return true;
+            }
+        }
+        i += 1u8;
+    }
+
+    // The block height isn't readable from here. A tx can't be included before its maturity
+    // and the price only goes down, so the price at the maturity is never below the price
+    // of the block the fill lands in. Without a maturity the taker pays START_PRICE.
+    let price = price_at(tx_maturity().unwrap_or(0));
+
+    assert(input_asset_id(0).unwrap().into() == BASE_ASSET);
+    assert(output_asset_id(2).unwrap().into() == BASE_ASSET);
+    assert(output_asset_id(0).unwrap().into() == QUOTE_ASSET);
+
+    let base_output_amount = output_amount(2);
+
+    let quote_output_amount = output_amount(0);
+    let quote_output_to = output_asset_to(0).unwrap();
+
+    assert(base_output_amount > 0);
+    assert(quote_to_base_amount(quote_output_amount, price) >= base_output_amount);
+    assert(unsold_stays(BASE_ASSET));
+
+    match output_type(0) {
+        Output::Coin => (),
+        _ => revert(0),
+    };
+    assert(quote_output_to == MAKER.into());
+
+    true
+}
+
+// Output 2 is the only coin of `asset` leaving and output 3 returns its change here, so the
+// taker gets no more of the predicate's coins than the fill pays for
+fn unsold_stays(asset: b256) -> bool {
+    match output_type(3) {
+        Output::Change => (),
+        _ => return false,
+    };
+    if output_asset_id(3).unwrap().into() != asset
+        || output_asset_to(3).unwrap() != predicate_address().into()
+    {
+        return false;
+    }
+    let mut i = 0;
+    let outputs = output_count();
+    while i < outputs {
+        if i != 2 {
+            match output_type(i) {
+                Output::Coin => {
+                    if output_asset_id(i).unwrap().into() == asset {
+                        return false;
+                    }
+                },
+                _ => (),
+            };
+        }
+        i += 1;
+    }
+    true
+}
+
+fn price_at(height: u32) -> u64 {
+    if height <= START_BLOCK {
+        return START_PRICE;
+    }
+    if height >= END_BLOCK {
+        return END_PRICE;
+    }
+    let elapsed = (height - START_BLOCK).as_u64();
+    let duration = (END_BLOCK - START_BLOCK).as_u64();
+    START_PRICE - (START_PRICE - END_PRICE).mul_div(elapsed, duration)
+}
+
+fn quote_to_base_amount(amount: u64, price: u64) -> u64 {
+    let (up, down) = decimals_scale();
+    let base = U256::from((0, 0, 0, amount)) * up / (U256::from((0, 0, 0, price)) * down);
+    base.as_u64().unwrap()
+}
+
+// 10^(BASE_DECIMALS + PRICE_DECIMALS - QUOTE_DECIMALS) as up / down, the exponent goes
+// negative when the quote asset has more decimals
+fn decimals_scale() -> (U256, U256) {
+    let up = BASE_DECIMALS + PRICE_DECIMALS;
+    if up >= QUOTE_DECIMALS {
+        (pow10(up - QUOTE_DECIMALS), U256::from((0, 0, 0, 1)))
+    } else {
+        (U256::from((0, 0, 0, 1)), pow10(QUOTE_DECIMALS - up))
+    }
+}
+
+fn pow10(exp: u32) -> U256 {
+    let ten = U256::from((0, 0, 0, 10));
+    let mut result = U256::from((0, 0, 0, 1));
+    let mut i = 0;
+    while i < exp {
+        result = result * ten;
+        i += 1;
+    }
+    result
+}
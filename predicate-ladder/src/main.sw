@@ -0,0 +1,172 @@
+predicate;
+
+// 🟤 PRICE LADDER PREDICATE
+
+use std::u256::U256;
+use std::auth::predicate_address;
+use std::outputs::{Output, output_asset_id, output_amount, output_count, output_type, output_asset_to};
+use std::inputs::{input_amount, input_asset_id, input_count, input_coin_owner};
+use std::constants::{ZERO_B256};
+
+// Maker(Alice) sells BTC in up to 5 levels, SIZES[0] at PRICES[0], then SIZES[1] at PRICES[1]...
+// Taker(Bob) pays each level's price for the part of his fill that falls in it. A fill spends
+// exactly one coin of the predicate and what was already sold is the total of SIZES minus that
+// coin. PRICES go up level by level, so a coin that holds less than the rest of the ladder (one
+// sent to the predicate on top of it) only sells at higher prices.
+
+configurable {
+    QUOTE_ASSET: b256 = ZERO_B256, // Asset that provides taker(Bob)
+    BASE_ASSET: b256 = ZERO_B256, // Asset that provides maker(Alice)
+    MAKER: Address = Address::from(ZERO_B256), // Order owner
+    PRICES: [u64; 5] = [0, 0, 0, 0, 0],
+    SIZES: [u64; 5] = [0, 0, 0, 0, 0], // base asset
+    LEVELS: u64 = 0, // how many of PRICES/SIZES are used
+    QUOTE_DECIMALS: u32 = 9,
+    BASE_DECIMALS: u32 = 9,
+    PRICE_DECIMALS: u32 = 9, // optional
+    NONCE: b256 = ZERO_B256, // optional, only there to give otherwise identical orders distinct roots
+}
+
+fn min(a: u64, b: u64) -> u64 {
+    if a < b { a } else { b }
+}
+
+fn max(a: u64, b: u64) -> u64 {
+    if a > b { a } else { b }
+}
+
+fn main() -> bool {
+    assert(LEVELS > 0 && LEVELS <= 5 && MAKER.into() != ZERO_B256);
+    let mut level = 1;
+    while level < LEVELS {
+        assert(PRICES[level] > PRICES[level - 1]);
+        level += 1;
+    }
+
+    let mut i = 0u8;
+    let inputs: u8 = input_count();
+    while i < inputs  {
+        // contract and message inputs have no owner, they can't be the maker's
+        if let Some(owner) = input_coin_owner(i.as_u64()) {
+            if owner == MAKER {
+                return true;
+            }
+        }
+        i += 1u8;
+    }
+
+    assert(input_asset_id(0).unwrap().into() == BASE_ASSET);
+    assert(output_asset_id(2).unwrap().into() == BASE_ASSET);
+    assert(output_asset_id(0).unwrap().into() == QUOTE_ASSET);
+
+    let base_output_amount = output_amount(2);
+
+    let quote_output_amount = output_amount(0);
+    let quote_output_to = output_asset_to(0).unwrap();
+
+    // The fill is priced by input 0, the only coin of the predicate it spends
+    let own_address = predicate_address();
+    assert(input_coin_owner(0).unwrap() == own_address);
+    let mut i = 1u8;
+    while i < inputs {
+        if let Some(owner) = input_coin_owner(i.as_u64()) {
+            assert(owner != own_address);
+        }
+        i += 1u8;
+    }
+    let balance = input_amount(0).unwrap();
+    assert(base_output_amount > 0 && base_output_amount <= balance);
+    assert(unsold_stays(BASE_ASSET));
+
+    let mut total = 0;
+    let mut level = 0;
+    while level < LEVELS {
+        total += SIZES[level];
+        level += 1;
+    }
+    let consumed = if balance >= total { 0 } else { total - balance };
+    let fill_start = consumed;
+    let fill_end = consumed + base_output_amount;
+
+    // sum of base * price over the levels the fill crosses
+    let mut value = U256::from((0, 0, 0, 0));
+    let mut level_start = 0;
+    let mut level = 0;
+    while level < LEVELS {
+        let level_end = level_start + SIZES[level];
+        let from = max(level_start, fill_start);
+        let to = min(level_end, fill_end);
+        if to > from {
+            value = value + U256::from((0, 0, 0, to - from)) * U256::from((0, 0, 0, PRICES[level]));
+        }
+        level_start = level_end;
+        level += 1;
+    }
+    // fills can't go past the last level
+    assert(fill_end <= level_start);
+
+    // quote * 10^(BASE_DECIMALS + PRICE_DECIMALS - QUOTE_DECIMALS) >= value, with a negative
+    // exponent moving to the right hand side
+    let (up, down) = decimals_scale();
+    assert(U256::from((0, 0, 0, quote_output_amount)) * up >= value * down);
+
+    match output_type(0) {
+        Output::Coin => (),
+        _ => revert(0),
+    };
+    assert(quote_output_to == MAKER.into());
+
+    true
+}
+
+// Output 2 is the only coin of `asset` leaving and output 3 returns its change here, so the
+// taker gets no more of the predicate's coins than the fill pays for
+fn unsold_stays(asset: b256) -> bool {
+    match output_type(3) {
+        Output::Change => (),
+        _ => return false,
+    };
+    if output_asset_id(3).unwrap().into() != asset
+        || output_asset_to(3).unwrap() != predicate_address().into()
+    {
+        return false;
+    }
+    let mut i = 0;
+    let outputs = output_count();
+    while i < outputs {
+        if i != 2 {
+            match output_type(i) {
+                Output::Coin => {
+                    if output_asset_id(i).unwrap().into() == asset {
+                        return false;
+                    }
+                },
+                _ => (),
+            };
+        }
+        i += 1;
+    }
+    true
+}
+
+// 10^(BASE_DECIMALS + PRICE_DECIMALS - QUOTE_DECIMALS) as up / down, the exponent goes
+// negative when the quote asset has more decimals
+fn decimals_scale() -> (U256, U256) {
+    let up = BASE_DECIMALS + PRICE_DECIMALS;
+    if up >= QUOTE_DECIMALS {
+        (pow10(up - QUOTE_DECIMALS), U256::from((0, 0, 0, 1)))
+    } else {
+        (U256::from((0, 0, 0, 1)), pow10(QUOTE_DECIMALS - up))
+    }
+}
+
+fn pow10(exp: u32) -> U256 {
+    let ten = U256::from((0, 0, 0, 10));
+    let mut result = U256::from((0, 0, 0, 1));
+    let mut i = 0;
+    while i < exp {
+        result = result * ten;
+        i += 1;
+    }
+    result
+}
@@ -0,0 +1,116 @@
+predicate;
+
+// 🟣 OCO PREDICATE
+
+use std::u256::U256;
+use std::outputs::{Output, output_asset_id, output_amount, output_type, output_asset_to};
+use std::inputs::{input_amount, input_asset_id, input_count, input_coin_owner};
+use std::constants::{ZERO_B256};
+
+// Maker(Alice) exits a BTC position either way: a take-profit sell anyone can fill at
+// TAKE_PROFIT_PRICE or better, or a stop sell at STOP_PRICE only STOP_KEEPER can fill.
+// Predicates can't see the market, the keeper watches it and takes the stop once it trades
+// through. Fills take the whole balance, so once one leg fills the other is gone.
+
+configurable {
+    QUOTE_ASSET: b256 = ZERO_B256, // Asset that provides taker(Bob)
+    BASE_ASSET: b256 = ZERO_B256, // Asset that provides maker(Alice)
+    MAKER: Address = Address::from(ZERO_B256), // Order owner
+    TAKE_PROFIT_PRICE: u64 = 0,
+    STOP_PRICE: u64 = 0,
+    STOP_KEEPER: Address = Address::from(ZERO_B256), // the only one who can fill the stop leg
+    QUOTE_DECIMALS: u32 = 9,
+    BASE_DECIMALS: u32 = 9,
+    PRICE_DECIMALS: u32 = 9, // optional
+    NONCE: b256 = ZERO_B256, // optional, only there to give otherwise identical orders distinct roots
+}
+
+impl u64 {
+    pub fn mul_div(self, mul_to: u64, div_to: u64) -> u64 {
+        let mul_result = U256::from((0, 0, 0, self)) * U256::from((0, 0, 0, mul_to));
+        let div_result = mul_result / U256::from((0, 0, 0, div_to));
+        div_result.as_u64().unwrap()
+    }
+}
+
+fn main() -> bool {
+    assert(TAKE_PROFIT_PRICE > STOP_PRICE && STOP_PRICE > 0);
+    assert(MAKER.into() != ZERO_B256 && STOP_KEEPER.into() != ZERO_B256);
+
+    let mut i = 0u8;
+    let inputs: u8 = input_count();
+    let mut signed_by_keeper = false;
+    while i < inputs  {
+        // contract and message inputs have no owner, they can't be the maker's or the keeper's
+        if let Some(owner) = input_coin_owner(i.as_u64()) {
+            if owner == MAKER {
+                return true;
+            }
+            if owner == STOP_KEEPER {
+                signed_by_keeper = true;
+            }
+        }
+        i += 1u8;
+    }
+
+    assert(input_asset_id(0).unwrap().into() == BASE_ASSET);
+    assert(output_asset_id(2).unwrap().into() == BASE_ASSET);
+    assert(output_asset_id(0).unwrap().into() == QUOTE_ASSET);
+
+    let base_output_amount = output_amount(2);
+
+    let quote_output_amount = output_amount(0);
+    let quote_output_to = output_asset_to(0).unwrap();
+
+    // One leg takes everything, nothing returns to the predicate as change
+    let mut offered = 0;
+    let mut i = 0u8;
+    while i < inputs {
+        if let Some(asset_id) = input_asset_id(i.as_u64()) {
+            if asset_id.into() == BASE_ASSET {
+                offered += input_amount(i.as_u64()).unwrap();
+            }
+        }
+        i += 1u8;
+    }
+    assert(base_output_amount > 0 && base_output_amount == offered);
+
+    let price = if signed_by_keeper { STOP_PRICE } else { TAKE_PROFIT_PRICE };
+    assert(quote_to_base_amount(quote_output_amount, price) >= base_output_amount);
+
+    match output_type(0) {
+        Output::Coin => (),
+        _ => revert(0),
+    };
+    assert(quote_output_to == MAKER.into());
+
+    true
+}
+
+fn quote_to_base_amount(amount: u64, price: u64) -> u64 {
+    let (up, down) = decimals_scale();
+    let base = U256::from((0, 0, 0, amount)) * up / (U256::from((0, 0, 0, price)) * down);
+    base.as_u64().unwrap()
+}
+
+// 10^(BASE_DECIMALS + PRICE_DECIMALS - QUOTE_DECIMALS) as up / down, the exponent goes
+// negative when the quote asset has more decimals
+fn decimals_scale() -> (U256, U256) {
+    let up = BASE_DECIMALS + PRICE_DECIMALS;
+    if up >= QUOTE_DECIMALS {
+        (pow10(up - QUOTE_DECIMALS), U256::from((0, 0, 0, 1)))
+    } else {
+        (U256::from((0, 0, 0, 1)), pow10(QUOTE_DECIMALS - up))
+    }
+}
+
+fn pow10(exp: u32) -> U256 {
+    let ten = U256::from((0, 0, 0, 10));
+    let mut result = U256::from((0, 0, 0, 1));
+    let mut i = 0;
+    while i < exp {
+        result = result * ten;
+        i += 1;
+    }
+    result
+}
@@ -0,0 +1,169 @@
+predicate;
+
+// 🔵 RFQ PREDICATE
+
+use std::u256::U256;
+use std::b512::B512;
+use std::auth::predicate_address;
+use std::ecr::ec_recover_address;
+use std::hash::{Hash, Hasher};
+use std::outputs::{Output, output_asset_id, output_amount, output_count, output_type, output_asset_to};
+use std::inputs::{input_asset_id, input_count, input_coin_owner};
+use std::constants::{ZERO_B256};
+use std::tx::{tx_script_bytecode_hash, tx_script_data};
+
+// Maker(Alice) funds the predicate once and streams signed quotes off-chain
+// Taker(Bob) fills with the latest quote passed as predicate data
+// A quote is signed for one coin of the predicate and the fill spends it first, so every
+// quote fills once. Alice revokes the quotes out for a coin by moving it.
+
+// gtf arguments of a coin input's utxo id, std::inputs doesn't read them
+const GTF_INPUT_COIN_TX_ID = 0x201;
+const GTF_INPUT_COIN_OUTPUT_INDEX = 0x202;
+
+configurable {
+    QUOTE_ASSET: b256 = ZERO_B256,
+    BASE_ASSET: b256 = ZERO_B256,
+    MAKER: Address = Address::from(ZERO_B256), // Order owner, signs the quotes
+    BUY: bool = false, // false: holds BASE and sells it (asks), true: holds QUOTE and buys (bids)
+    QUOTE_DECIMALS: u32 = 9,
+    BASE_DECIMALS: u32 = 9,
+    PRICE_DECIMALS: u32 = 9, // optional
+    EXPIRY_SCRIPT_HASH: b256 = ZERO_B256, // sha256 of the expiry-script bytecode
+}
+
+impl u64 {
+    pub fn mul_div(self, mul_to: u64, div_to: u64) -> u64 {
+        let mul_result = U256::from((0, 0, 0, self)) * U256::from((0, 0, 0, mul_to));
+        let div_result = mul_result / U256::from((0, 0, 0, div_to));
+        div_result.as_u64().unwrap()
+    }
+}
+
+// price: like order prices, max_size: most the predicate gives in the fill, in its own asset,
+// expiry: block height or TAI64 timestamp, nonce: tells quotes apart for the maker
+fn main(price: u64, max_size: u64, expiry: u64, nonce: u64, signature: B512) -> bool {
+    assert(MAKER.into() != ZERO_B256);
+
+    let mut i = 0u8;
+    let inputs: u8 = input_count();
+    while i < inputs  {
+        // contract and message inputs have no owner, they can't be the maker's
+        if let Some(owner) = input_coin_owner(i.as_u64()) {
+            if owner == MAKER {
+                return true;
+            }
+        }
+        i += 1u8;
+    }
+
+    // The quoted coin goes first
+    assert(input_coin_owner(0).unwrap() == predicate_address());
+    let coin_tx_id = __gtf::<raw_ptr>(0, GTF_INPUT_COIN_TX_ID).read::<b256>();
+    let coin_output_index = __gtf::<u64>(0, GTF_INPUT_COIN_OUTPUT_INDEX);
+
+    // The quote has to come from the maker, for this pair, side and coin
+    let side = if BUY { 1 } else { 0 };
+    let mut hasher = Hasher::new();
+    BASE_ASSET.hash(hasher);
+    QUOTE_ASSET.hash(hasher);
+    side.hash(hasher);
+    coin_tx_id.hash(hasher);
+    coin_output_index.hash(hasher);
+    price.hash(hasher);
+    max_size.hash(hasher);
+    expiry.hash(hasher);
+    nonce.hash(hasher);
+    assert(ec_recover_address(signature, hasher.sha256()).unwrap() == MAKER);
+
+    // Quotes always expire, checked by the expiry script like expiring orders
+    assert(price > 0 && expiry != 0);
+    assert(tx_script_bytecode_hash() == EXPIRY_SCRIPT_HASH);
+    assert(tx_script_data::<u64>() == expiry);
+
+    let (offered_asset, asked_asset) = if BUY {
+        (QUOTE_ASSET, BASE_ASSET)
+    } else {
+        (BASE_ASSET, QUOTE_ASSET)
+    };
+    assert(input_asset_id(0).unwrap().into() == offered_asset);
+    assert(output_asset_id(2).unwrap().into() == offered_asset);
+    assert(output_asset_id(0).unwrap().into() == asked_asset);
+
+    let offered_amount = output_amount(2);
+    let asked_amount = output_amount(0);
+    assert(offered_amount > 0 && offered_amount <= max_size);
+    assert(unsold_stays(offered_asset));
+
+    if BUY {
+        assert(asked_amount >= quote_to_base_amount(offered_amount, price));
+    } else {
+        assert(quote_to_base_amount(asked_amount, price) >= offered_amount);
+    }
+
+    match output_type(0) {
+        Output::Coin => (),
+        _ => revert(0),
+    };
+    assert(output_asset_to(0).unwrap() == MAKER.into());
+
+    true
+}
+
+// Output 2 is the only coin of `asset` leaving and output 3 returns its change here, so the
+// taker gets no more of the coin than `max_size`, and new quotes can be signed for the change
+fn unsold_stays(asset: b256) -> bool {
+    match output_type(3) {
+        Output::Change => (),
+        _ => return false,
+    };
+    if output_asset_id(3).unwrap().into() != asset
+        || output_asset_to(3).unwrap() != predicate_address().into()
+    {
+        return false;
+    }
+    let mut i = 0;
+    let outputs = output_count();
+    while i < outputs {
+        if i != 2 {
+            match output_type(i) {
+                Output::Coin => {
+                    if output_asset_id(i).unwrap().into() == asset {
+                        return false;
+                    }
+                },
+                _ => (),
+            };
+        }
+        i += 1;
+    }
+    true
+}
+
+fn quote_to_base_amount(amount: u64, price: u64) -> u64 {
+    let (up, down) = decimals_scale();
+    let base = U256::from((0, 0, 0, amount)) * up / (U256::from((0, 0, 0, price)) * down);
+    base.as_u64().unwrap()
+}
+
+// 10^(BASE_DECIMALS + PRICE_DECIMALS - QUOTE_DECIMALS) as up / down, the exponent goes
+// negative when the quote asset has more decimals
+fn decimals_scale() -> (U256, U256) {
+    let up = BASE_DECIMALS + PRICE_DECIMALS;
+    if up >= QUOTE_DECIMALS {
+        (pow10(up - QUOTE_DECIMALS), U256::from((0, 0, 0, 1)))
+    } else {
+        (U256::from((0, 0, 0, 1)), pow10(QUOTE_DECIMALS - up))
+    }
+}
+
+fn pow10(exp: u32) -> U256 {
+    let ten = U256::from((0, 0, 0, 10));
+    let mut result = U256::from((0, 0, 0, 1));
+    let mut i = 0;
+    while i < exp {
+        result = result * ten;
+        i += 1;
+    }
+    result
+}
@@ -2,10 +2,12 @@ predicate;
 
 // 🔴 SELL PREDICATE
 
-use std::u128::U128;
+use std::u256::U256;
+use std::auth::predicate_address;
 use std::outputs::{Output, output_asset_id, output_amount, output_count, output_type, output_asset_to};
 use std::inputs::{input_amount, input_asset_id, input_count, input_coin_owner};
 use std::constants::{ZERO_B256};
+use std::tx::{tx_script_bytecode_hash, tx_script_data};
 
 // Maker(Alice) wants to exchange BTC for USDC
 // Taker(Bob) wants to exchange USDC for BTC 
@@ -19,12 +21,21 @@ configurable {
     QUOTE_DECIMALS: u32 = 9,
     PRICE_DECIMALS: u32 = 9, // optional
     MIN_FULFILL_BASE_AMOUNT: u64 = 1, // optional
+    EXPIRY: u64 = 0, // optional, block height or TAI64 timestamp, 0 = never expires
+    EXPIRY_SCRIPT_HASH: b256 = ZERO_B256, // optional, sha256 of the expiry-script bytecode
+    NONCE: b256 = ZERO_B256, // optional, only there to give otherwise identical orders distinct roots
+    FEE_RECIPIENT: Address = Address::from(ZERO_B256), // optional, gets FEE_BPS of every fill
+    FEE_BPS: u64 = 0, // optional, in basis points of what the taker pays the maker
+    RECIPIENT: Address = Address::from(ZERO_B256), // optional, gets the proceeds instead of MAKER
+    ALLOWED_TAKER: Address = Address::from(ZERO_B256), // optional, the only one who can fill, zero = anyone
+    ALL_OR_NONE: bool = false, // optional, only a fill taking the whole balance goes through
+    PRICE_DEN: u64 = 0, // optional, makes the price PRICE / PRICE_DEN quote units per base unit
 }
 
 impl u64 {
     pub fn mul_div(self, mul_to: u64, div_to: u64) -> u64 {
-        let mul_result = U128::from((0, self)) * U128::from((0, mul_to));
-        let div_result = mul_result / U128::from((0, div_to));
+        let mul_result = U256::from((0, 0, 0, self)) * U256::from((0, 0, 0, mul_to));
+        let div_result = mul_result / U256::from((0, 0, 0, div_to));
         div_result.as_u64().unwrap()
     }
 }
@@ -35,12 +46,37 @@ fn main() -> bool {
     let mut i = 0u8;
     let inputs: u8 = input_count();
     while i < inputs  {
-        if input_coin_owner(i.as_u64()).unwrap() == MAKER {
-            return true;
+        // contract and message inputs have no owner, they can't be the maker's
+        if let Some(owner) = input_coin_owner(i.as_u64()) {
+            if owner == MAKER {
+                return true;
+            }
         }
         i += 1u8;
     }
-    
+
+    // The block height/time isn't readable from here, expiring orders can only be filled
+    // by the expiry script, which reverts once EXPIRY has passed
+    if EXPIRY != 0 {
+        assert(tx_script_bytecode_hash() == EXPIRY_SCRIPT_HASH);
+        assert(tx_script_data::<u64>() == EXPIRY);
+    }
+
+    // Private orders need a coin signed for by the allowed taker
+    if ALLOWED_TAKER.into() != ZERO_B256 {
+        let mut signed_by_taker = false;
+        let mut i = 0u8;
+        while i < inputs {
+            if let Some(owner) = input_coin_owner(i.as_u64()) {
+                if owner == ALLOWED_TAKER {
+                    signed_by_taker = true;
+                }
+            }
+            i += 1u8;
+        }
+        assert(signed_by_taker);
+    }
+
     assert(input_asset_id(0).unwrap().into() == BASE_ASSET);
     assert(output_asset_id(2).unwrap().into() == BASE_ASSET);
     assert(output_asset_id(0).unwrap().into() == QUOTE_ASSET);
@@ -52,7 +88,11 @@ fn main() -> bool {
     
     let base_input_amount = input_amount(0).unwrap();
     
-    assert(quote_to_base_amount(quote_output_amount, PRICE) == base_output_amount);
+    // Cross-multiplied, nothing gets rounded: the maker gets at least base_output * price
+    let (num, den) = price_fraction();
+    let maker_gets = U256::from((0, 0, 0, quote_output_amount)) * den;
+    let maker_asks = U256::from((0, 0, 0, base_output_amount)) * num;
+    assert(maker_gets >= maker_asks);
 
     let limit = if base_input_amount >= MIN_FULFILL_BASE_AMOUNT {
         MIN_FULFILL_BASE_AMOUNT
@@ -61,19 +101,98 @@ fn main() -> bool {
     };
     assert(base_output_amount >= limit);
 
+    // All-or-none orders give out every coin they spend, nothing returns as change
+    if ALL_OR_NONE {
+        let mut offered = 0;
+        let mut i = 0u8;
+        while i < inputs {
+            if let Some(asset_id) = input_asset_id(i.as_u64()) {
+                if asset_id.into() == BASE_ASSET {
+                    offered += input_amount(i.as_u64()).unwrap();
+                }
+            }
+            i += 1u8;
+        }
+        assert(base_output_amount == offered);
+    }
+
+    assert(unsold_stays(BASE_ASSET));
+
     match output_type(0) {
         Output::Coin => (),
         _ => revert(0),
     };
-    assert(quote_output_to == MAKER.into());
+    // MAKER keeps the cancel rights, the proceeds may go elsewhere
+    let recipient = if RECIPIENT.into() == ZERO_B256 { MAKER } else { RECIPIENT };
+    assert(quote_output_to == recipient.into());
+
+    // The taker pays the fee on top, in the same asset, with the output after the change
+    if FEE_BPS != 0 {
+        match output_type(4) {
+            Output::Coin => (),
+            _ => revert(0),
+        };
+        assert(output_asset_id(4).unwrap().into() == QUOTE_ASSET);
+        assert(output_asset_to(4).unwrap() == FEE_RECIPIENT.into());
+        assert(output_amount(4) >= quote_output_amount.mul_div(FEE_BPS, 10_000));
+    }
+    true
+}
+
+
+// Output 2 is the only coin of `asset` leaving and output 3 returns its change here, so the
+// taker gets no more of the predicate's coins than the fill pays for
+fn unsold_stays(asset: b256) -> bool {
+    match output_type(3) {
+        Output::Change => (),
+        _ => return false,
+    };
+    if output_asset_id(3).unwrap().into() != asset
+        || output_asset_to(3).unwrap() != predicate_address().into()
+    {
+        return false;
+    }
+    let mut i = 0;
+    let outputs = output_count();
+    while i < outputs {
+        if i != 2 {
+            match output_type(i) {
+                Output::Coin => {
+                    if output_asset_id(i).unwrap().into() == asset {
+                        return false;
+                    }
+                },
+                _ => (),
+            };
+        }
+        i += 1;
+    }
     true
 }
 
+// PRICE / PRICE_DEN quote units per base unit. With PRICE_DEN 0 it's PRICE scaled down by
+// 10^(BASE_DECIMALS + PRICE_DECIMALS - QUOTE_DECIMALS), an exponent that goes negative when the
+// quote asset has more decimals, so the scale moves to whichever side of the fraction fits it.
+fn price_fraction() -> (U256, U256) {
+    let price = U256::from((0, 0, 0, PRICE));
+    if PRICE_DEN != 0 {
+        return (price, U256::from((0, 0, 0, PRICE_DEN)));
+    }
+    let up = BASE_DECIMALS + PRICE_DECIMALS;
+    if up >= QUOTE_DECIMALS {
+        (price, pow10(up - QUOTE_DECIMALS))
+    } else {
+        (price * pow10(QUOTE_DECIMALS - up), U256::from((0, 0, 0, 1)))
+    }
+}
 
-fn quote_to_base_amount(amount: u64, price: u64) -> u64 {
-    amount.mul_div(
-        10_u64
-            .pow(BASE_DECIMALS + PRICE_DECIMALS - QUOTE_DECIMALS),
-        price,
-    )
+fn pow10(exp: u32) -> U256 {
+    let ten = U256::from((0, 0, 0, 10));
+    let mut result = U256::from((0, 0, 0, 1));
+    let mut i = 0;
+    while i < exp {
+        result = result * ten;
+        i += 1;
+    }
+    result
 }
@@ -1,31 +1,134 @@
 contract;
 
 mod math;
+mod template;
 
 use math::*;
+use template::*;
 
 use std::logging::log;
 use std::call_frames::msg_asset_id;
 use std::context::msg_amount;
 use std::asset::transfer_to_address;
 use std::constants::ZERO_B256;
-use std::u128::U128;
+use std::u256::U256;
+use std::bytes::Bytes;
+use std::bytes_conversions::b256::*;
+use std::bytes_conversions::u64::*;
+use std::storage::storage_bytes::*;
+use std::storage::storage_vec::*;
+use std::hash::{Hash, Hasher};
+use std::inputs::{input_amount, input_asset_id, input_coin_owner, input_count};
+use std::outputs::{Output, output_amount, output_asset_id, output_asset_to, output_count, output_type};
 
 configurable {
-    BASE_ASSET: AssetId = AssetId::from(ZERO_B256),
-    QUOTE_ASSET: AssetId = AssetId::from(ZERO_B256),
-    BASE_ASSET_DECIMALS: u32 = 9,
-    QUOTE_ASSET_DECIMALS: u32 = 9,
     PRICE_DECIMALS: u32 = 9,
+    PREDICATE_VERSION: u64 = 1, // the predicate-buy/predicate-sell build orders are created for
+    OWNER: Address = Address::from(ZERO_B256), // uploads the predicate templates, lists markets
+}
+
+storage {
+    // predicate-buy/predicate-sell bytecode with the default configurables, see `order_predicate_root`
+    buy_predicate: StorageBytes = StorageBytes {},
+    sell_predicate: StorageBytes = StorageBytes {},
+    buy_offsets: Option<PredicateOffsets> = Option::None,
+    sell_offsets: Option<PredicateOffsets> = Option::None,
+    // the same for predicate-dutch-auction, predicate-oco and predicate-ladder
+    auction_predicate: StorageBytes = StorageBytes {},
+    oco_predicate: StorageBytes = StorageBytes {},
+    ladder_predicate: StorageBytes = StorageBytes {},
+    auction_offsets: Option<AuctionOffsets> = Option::None,
+    oco_offsets: Option<OcoOffsets> = Option::None,
+    ladder_offsets: Option<LadderOffsets> = Option::None,
+    // the markets orders can be created in, a market's id is its index
+    markets: StorageVec<MarketInfo> = StorageVec {},
 }
 
 abi ProxyContract {
-    #[payable]
+    #[storage(read), payable]
     fn create_order(
+        market_id: u64,
         price: u64,
+        price_den: u64,
         predicate_root: Address,
-        min_fulfill_base_amount: Option<u64>,
-    );
+        min_fulfill_amount: u64,
+        expiry: u64,
+        expiry_script_hash: b256,
+        nonce: b256,
+        fee_recipient: Address,
+        fee_bps: u64,
+        recipient: Address,
+        allowed_taker: Address,
+        all_or_none: bool,
+    ) -> b256;
+
+    #[storage(read), payable]
+    fn create_auction(
+        market_id: u64,
+        start_price: u64,
+        end_price: u64,
+        start_block: u32,
+        end_block: u32,
+        predicate_root: Address,
+        nonce: b256,
+    ) -> b256;
+
+    #[storage(read), payable]
+    fn create_oco_order(
+        market_id: u64,
+        take_profit_price: u64,
+        stop_price: u64,
+        stop_keeper: Address,
+        predicate_root: Address,
+        nonce: b256,
+    ) -> b256;
+
+    #[storage(read), payable]
+    fn create_ladder_order(
+        market_id: u64,
+        prices: [u64; 5],
+        sizes: [u64; 5],
+        levels: u64,
+        predicate_root: Address,
+        nonce: b256,
+    ) -> b256;
+
+    #[storage(read)]
+    fn fulfill_order(market_id: u64, predicate_root: Address) -> u64;
+
+    #[storage(read)]
+    fn cancel_order(market_id: u64, predicate_root: Address, maker: Address);
+
+    #[storage(read, write)]
+    fn set_predicate_template(order_type: OrderType, bytecode: Bytes, offsets: PredicateOffsets);
+
+    #[storage(read, write)]
+    fn set_auction_template(bytecode: Bytes, offsets: AuctionOffsets);
+
+    #[storage(read, write)]
+    fn set_oco_template(bytecode: Bytes, offsets: OcoOffsets);
+
+    #[storage(read, write)]
+    fn set_ladder_template(bytecode: Bytes, offsets: LadderOffsets);
+
+    #[storage(read, write)]
+    fn list_market(
+        base_asset: AssetId,
+        quote_asset: AssetId,
+        base_asset_decimals: u32,
+        quote_asset_decimals: u32,
+        tick_size: u64,
+        lot_size: u64,
+    ) -> u64;
+
+    #[storage(read)]
+    fn market_count() -> u64;
+
+    #[storage(read)]
+    fn market_info(market_id: u64) -> MarketInfo;
+
+    #[storage(read)]
+    fn template_root(order_type: OrderType) -> Address;
 }
 
 enum OrderType {
@@ -35,57 +138,809 @@ enum OrderType {
 enum Errors {
     InvalidPayment: (),
     InvalidPredicateRoot: (),
+    InvalidFee: (),
+    InvalidAuction: (),
+    InvalidOcoOrder: (),
+    InvalidLadder: (),
+    InvalidFill: (),
+    InvalidCancel: (),
+    PredicateRootMismatch: (),
+    InvalidTemplate: (),
+    Unauthorized: (),
+    InvalidMarket: (),
+    InvalidTick: (),
+    InvalidLot: (),
+    InvalidPrice: (),
+}
+
+// A market the proxy serves, what predicates for its orders have to be configured with.
+// create_order takes scaled prices in multiples of tick_size and sell orders in multiples of
+// lot_size.
+struct MarketInfo {
+    base_asset: AssetId,
+    quote_asset: AssetId,
+    base_asset_decimals: u32,
+    quote_asset_decimals: u32,
+    price_decimals: u32,
+    tick_size: u64,
+    lot_size: u64,
+}
+
+struct MarketListedEvent {
+    market_id: u64,
+    market: MarketInfo,
 }
 
 struct CreateOrderEvent {
+    order_id: b256, // see `compute_order_id`
+    market_id: u64,
     predicate_root: Address,
     maker: Identity,
     price: u64, //quote_asset_price / base_asset_price * 10.pow(9 + base_asset_decimals - quote_asset_decimals)
+    price_den: u64, // when not 0 the price is price / price_den quote units per base unit instead
+    base_asset: AssetId,
+    quote_asset: AssetId,
+    base_amount: u64,
+    order_type: OrderType,
+    min_fulfill_amount: u64, // MIN_FULFILL_QUOTE_AMOUNT of a buy order, MIN_FULFILL_BASE_AMOUNT of a sell order
+    expiry: u64, // block height or TAI64 timestamp the predicate stops accepting fills at, 0 = never
+    expiry_script_hash: b256, // the predicate's EXPIRY_SCRIPT_HASH, zero when it never expires
+    nonce: b256, // the predicate's NONCE, tells apart orders with the same terms
+    fee_recipient: Address, // gets fee_bps of what every fill pays the maker, zero = no fee
+    fee_bps: u64,
+    recipient: Address, // the predicate's RECIPIENT, where fills pay the proceeds, zero = the maker
+    allowed_taker: Address, // the only address that can fill, zero = anyone
+    private: bool, // set when allowed_taker is, public order books should leave these out
+    all_or_none: bool, // the order can only be filled whole
+    base_asset_decimals: u32,
+    quote_asset_decimals: u32,
+    price_decimals: u32,
+    predicate_version: u64, // which predicate-buy/predicate-sell build the fields above configure
+}
+
+// A dutch auction sell order, the asked price falls linearly from start_price at start_block
+// to end_price at end_block and stays there
+struct CreateAuctionEvent {
+    order_id: b256, // see `compute_order_id`, with start_price as the price
+    market_id: u64,
+    predicate_root: Address,
+    maker: Identity,
+    base_asset: AssetId,
+    quote_asset: AssetId,
+    base_amount: u64,
+    start_price: u64,
+    end_price: u64,
+    start_block: u32,
+    end_block: u32,
+    nonce: b256,
+}
+
+// A one-cancels-other sell order, filled whole either at take_profit_price by anyone or at
+// stop_price by stop_keeper
+struct CreateOcoOrderEvent {
+    order_id: b256, // see `compute_order_id`, with take_profit_price as the price
+    market_id: u64,
+    predicate_root: Address,
+    maker: Identity,
+    base_asset: AssetId,
+    quote_asset: AssetId,
+    base_amount: u64,
+    take_profit_price: u64,
+    stop_price: u64,
+    stop_keeper: Address,
+    nonce: b256,
+}
+
+// A sell order in price levels, the first `levels` entries of prices/sizes: sizes[0] of the
+// base asset at prices[0], then sizes[1] at prices[1] and so on
+struct CreateLadderOrderEvent {
+    order_id: b256, // see `compute_order_id`, with prices[0] as the price
+    market_id: u64,
+    predicate_root: Address,
+    maker: Identity,
     base_asset: AssetId,
     quote_asset: AssetId,
     base_amount: u64,
+    prices: [u64; 5],
+    sizes: [u64; 5],
+    levels: u64,
+    nonce: b256,
+}
+
+// A fill of a buy/sell order, logged by the proxy call the taker puts in the fill tx. Amounts
+// are read off the tx: base_amount and quote_amount are what the taker and the maker get
+struct FillEvent {
+    market_id: u64,
+    predicate_root: Address,
+    taker: Address,
     order_type: OrderType,
-    min_fulfill_base_amount: Option<u64>,
+    base_asset: AssetId,
+    quote_asset: AssetId,
+    base_amount: u64,
+    quote_amount: u64,
+    remaining_amount: u64, // what the spent predicate coins return to the predicate as change
+}
+
+// A cancelled order, logged by the proxy call the maker puts in the cancel tx. The amounts are
+// the predicate coins the tx spends, all of them go back to the maker
+struct CancelOrderEvent {
+    market_id: u64,
+    predicate_root: Address,
+    maker: Address,
+    base_asset: AssetId,
+    quote_asset: AssetId,
+    base_amount: u64,
+    quote_amount: u64,
 }
 
 impl ProxyContract for Contract {
-    #[payable]
+    #[storage(read), payable]
     fn create_order(
+        market_id: u64,
         price: u64,
+        price_den: u64,
         predicate_root: Address,
-        min_fulfill_base_amount: Option<u64>,
-    ) {
+        min_fulfill_amount: u64,
+        expiry: u64,
+        expiry_script_hash: b256,
+        nonce: b256,
+        fee_recipient: Address,
+        fee_bps: u64,
+        recipient: Address,
+        allowed_taker: Address,
+        all_or_none: bool,
+    ) -> b256 {
         let payment_amount = msg_amount();
         let payment_asset = msg_asset_id();
         let maker = msg_sender().unwrap();
+        let market = read_market(market_id);
 
         require(
-            payment_asset == BASE_ASSET || payment_asset == QUOTE_ASSET,
+            payment_asset == market.base_asset || payment_asset == market.quote_asset,
             Errors::InvalidPayment,
         );
         require(
             predicate_root != Address::from(ZERO_B256),
             Errors::InvalidPredicateRoot,
         );
+        require(
+            fee_bps <= 10_000 && (fee_bps == 0 || fee_recipient != Address::from(ZERO_B256)),
+            Errors::InvalidFee,
+        );
+
+        require(price > 0, Errors::InvalidPrice);
+        // rational prices have no scale to put a tick on
+        require(
+            price_den != 0 || price % market.tick_size == 0,
+            Errors::InvalidTick,
+        );
 
-        let (base_amount, order_type) = if payment_asset == BASE_ASSET {
+        let (base_amount, order_type) = if payment_asset == market.base_asset {
+            require(payment_amount % market.lot_size == 0, Errors::InvalidLot);
             (payment_amount, OrderType::SELL)
         } else {
-            (quote_to_base_amount(payment_amount, price), OrderType::BUY)
+            (
+                quote_to_base_amount(market, payment_amount, price, price_den),
+                OrderType::BUY,
+            )
         };
 
-        log(CreateOrderEvent {
+        let order_id = compute_order_id(
+            market,
             predicate_root,
             maker,
             price,
-            base_asset: BASE_ASSET,
-            quote_asset: QUOTE_ASSET,
+            nonce,
+            payment_amount,
+        );
+        let event = CreateOrderEvent {
+            order_id,
+            market_id,
+            predicate_root,
+            maker,
+            price,
+            price_den,
+            base_asset: market.base_asset,
+            quote_asset: market.quote_asset,
             base_amount,
             order_type,
-            min_fulfill_base_amount,
-        });
+            min_fulfill_amount,
+            expiry,
+            expiry_script_hash,
+            nonce,
+            fee_recipient,
+            fee_bps,
+            recipient,
+            allowed_taker,
+            private: allowed_taker != Address::from(ZERO_B256),
+            all_or_none,
+            base_asset_decimals: market.base_asset_decimals,
+            quote_asset_decimals: market.quote_asset_decimals,
+            price_decimals: market.price_decimals,
+            predicate_version: PREDICATE_VERSION,
+        };
+        require(
+            predicate_root == order_predicate_root(event),
+            Errors::PredicateRootMismatch,
+        );
+        log(event);
         transfer_to_address(predicate_root, payment_asset, payment_amount);
+        order_id
     }
+
+    #[storage(read), payable]
+    fn create_auction(
+        market_id: u64,
+        start_price: u64,
+        end_price: u64,
+        start_block: u32,
+        end_block: u32,
+        predicate_root: Address,
+        nonce: b256,
+    ) -> b256 {
+        let payment_amount = msg_amount();
+        let payment_asset = msg_asset_id();
+        let maker = msg_sender().unwrap();
+        let market = read_market(market_id);
+
+        require(payment_asset == market.base_asset, Errors::InvalidPayment);
+        require(
+            predicate_root != Address::from(ZERO_B256),
+            Errors::InvalidPredicateRoot,
+        );
+        require(
+            end_price > 0 && start_price >= end_price && start_block < end_block,
+            Errors::InvalidAuction,
+        );
+
+        let order_id = compute_order_id(
+            market,
+            predicate_root,
+            maker,
+            start_price,
+            nonce,
+            payment_amount,
+        );
+        let event = CreateAuctionEvent {
+            order_id,
+            market_id,
+            predicate_root,
+            maker,
+            base_asset: market.base_asset,
+            quote_asset: market.quote_asset,
+            base_amount: payment_amount,
+            start_price,
+            end_price,
+            start_block,
+            end_block,
+            nonce,
+        };
+        require(
+            predicate_root == auction_predicate_root(event, market),
+            Errors::PredicateRootMismatch,
+        );
+        log(event);
+        transfer_to_address(predicate_root, payment_asset, payment_amount);
+        order_id
+    }
+
+    #[storage(read), payable]
+    fn create_oco_order(
+        market_id: u64,
+        take_profit_price: u64,
+        stop_price: u64,
+        stop_keeper: Address,
+        predicate_root: Address,
+        nonce: b256,
+    ) -> b256 {
+        let payment_amount = msg_amount();
+        let payment_asset = msg_asset_id();
+        let maker = msg_sender().unwrap();
+        let market = read_market(market_id);
+
+        require(payment_asset == market.base_asset, Errors::InvalidPayment);
+        require(
+            predicate_root != Address::from(ZERO_B256),
+            Errors::InvalidPredicateRoot,
+        );
+        require(
+            take_profit_price > stop_price && stop_price > 0 && stop_keeper != Address::from(ZERO_B256),
+            Errors::InvalidOcoOrder,
+        );
+
+        let order_id = compute_order_id(
+            market,
+            predicate_root,
+            maker,
+            take_profit_price,
+            nonce,
+            payment_amount,
+        );
+        let event = CreateOcoOrderEvent {
+            order_id,
+            market_id,
+            predicate_root,
+            maker,
+            base_asset: market.base_asset,
+            quote_asset: market.quote_asset,
+            base_amount: payment_amount,
+            take_profit_price,
+            stop_price,
+            stop_keeper,
+            nonce,
+        };
+        require(
+            predicate_root == oco_predicate_root(event, market),
+            Errors::PredicateRootMismatch,
+        );
+        log(event);
+        transfer_to_address(predicate_root, payment_asset, payment_amount);
+        order_id
+    }
+
+    #[storage(read), payable]
+    fn create_ladder_order(
+        market_id: u64,
+        prices: [u64; 5],
+        sizes: [u64; 5],
+        levels: u64,
+        predicate_root: Address,
+        nonce: b256,
+    ) -> b256 {
+        let payment_amount = msg_amount();
+        let payment_asset = msg_asset_id();
+        let maker = msg_sender().unwrap();
+        let market = read_market(market_id);
+
+        require(payment_asset == market.base_asset, Errors::InvalidPayment);
+        require(
+            predicate_root != Address::from(ZERO_B256),
+            Errors::InvalidPredicateRoot,
+        );
+        require(levels > 0 && levels <= 5, Errors::InvalidLadder);
+        let mut total = 0;
+        let mut level = 0;
+        while level < levels {
+            require(prices[level] > 0 && sizes[level] > 0, Errors::InvalidLadder);
+            // prices go up, the predicate rejects every fill of a ladder that doesn't
+            require(
+                level == 0 || prices[level] > prices[level - 1],
+                Errors::InvalidLadder,
+            );
+            total += sizes[level];
+            level += 1;
+        }
+        // the predicate tells filled levels from its coin, it has to start full
+        require(payment_amount == total, Errors::InvalidLadder);
+
+        let order_id = compute_order_id(
+            market,
+            predicate_root,
+            maker,
+            prices[0],
+            nonce,
+            payment_amount,
+        );
+        let event = CreateLadderOrderEvent {
+            order_id,
+            market_id,
+            predicate_root,
+            maker,
+            base_asset: market.base_asset,
+            quote_asset: market.quote_asset,
+            base_amount: payment_amount,
+            prices,
+            sizes,
+            levels,
+            nonce,
+        };
+        require(
+            predicate_root == ladder_predicate_root(event, market),
+            Errors::PredicateRootMismatch,
+        );
+        log(event);
+        transfer_to_address(predicate_root, payment_asset, payment_amount);
+        order_id
+    }
+
+    // Goes into the fill tx after the predicate layout: [0] asked coin to the maker, [1] taker
+    // change, [2] offered coin to the taker, [3] predicate change. Returns the remaining amount.
+    #[storage(read)]
+    fn fulfill_order(market_id: u64, predicate_root: Address) -> u64 {
+        let market = read_market(market_id);
+        match output_type(0) {
+            Output::Coin => (),
+            _ => revert(0),
+        };
+        match output_type(2) {
+            Output::Coin => (),
+            _ => revert(0),
+        };
+        let offered_asset = output_asset_id(2).unwrap();
+        let asked_asset = output_asset_id(0).unwrap();
+        require(
+            (offered_asset == market.base_asset && asked_asset == market.quote_asset) || (offered_asset == market.quote_asset && asked_asset == market.base_asset),
+            Errors::InvalidFill,
+        );
+        let order_type = if offered_asset == market.base_asset {
+            OrderType::SELL
+        } else {
+            OrderType::BUY
+        };
+
+        // Only the predicate can let its coins go, spending them makes this a real fill
+        let mut spent = 0;
+        let mut i = 0u8;
+        let inputs: u8 = input_count();
+        while i < inputs {
+            if let Some(owner) = input_coin_owner(i.as_u64()) {
+                if owner == predicate_root && input_asset_id(i.as_u64()).unwrap() == offered_asset {
+                    spent += input_amount(i.as_u64()).unwrap();
+                }
+            }
+            i += 1u8;
+        }
+        let offered_amount = output_amount(2);
+        require(
+            spent > 0 && offered_amount > 0 && offered_amount <= spent,
+            Errors::InvalidFill,
+        );
+
+        // What isn't offered has to go back to the predicate, or it isn't the remaining amount
+        let mut change_to_predicate = false;
+        let mut i = 0;
+        let outputs = output_count();
+        while i < outputs {
+            match output_type(i) {
+                Output::Change => {
+                    if output_asset_id(i).unwrap() == offered_asset {
+                        change_to_predicate = output_asset_to(i).unwrap() == predicate_root.value;
+                    }
+                },
+                _ => (),
+            };
+            i += 1;
+        }
+        require(change_to_predicate, Errors::InvalidFill);
+
+        let asked_amount = output_amount(0);
+        let (base_amount, quote_amount) = match order_type {
+            OrderType::SELL => (offered_amount, asked_amount),
+            OrderType::BUY => (asked_amount, offered_amount),
+        };
+        let remaining_amount = spent - offered_amount;
+        log(FillEvent {
+            market_id,
+            predicate_root,
+            taker: Address::from(output_asset_to(2).unwrap()),
+            order_type,
+            base_asset: market.base_asset,
+            quote_asset: market.quote_asset,
+            base_amount,
+            quote_amount,
+            remaining_amount,
+        });
+        remaining_amount
+    }
+
+    // Goes into the cancel tx, where the maker spends the predicate coins through the MAKER-input
+    // bypass. Everything the tx pays out has to go to `maker`, which can't be true of a fill.
+    #[storage(read)]
+    fn cancel_order(market_id: u64, predicate_root: Address, maker: Address) {
+        let market = read_market(market_id);
+        let mut signed_by_maker = false;
+        let mut base_amount = 0;
+        let mut quote_amount = 0;
+        let mut i = 0u8;
+        let inputs: u8 = input_count();
+        while i < inputs {
+            if let Some(owner) = input_coin_owner(i.as_u64()) {
+                if owner == maker {
+                    signed_by_maker = true;
+                }
+                if owner == predicate_root {
+                    let asset_id = input_asset_id(i.as_u64()).unwrap();
+                    if asset_id == market.base_asset {
+                        base_amount += input_amount(i.as_u64()).unwrap();
+                    } else if asset_id == market.quote_asset {
+                        quote_amount += input_amount(i.as_u64()).unwrap();
+                    }
+                }
+            }
+            i += 1u8;
+        }
+        require(
+            signed_by_maker && base_amount + quote_amount > 0,
+            Errors::InvalidCancel,
+        );
+
+        let mut i = 0;
+        let outputs = output_count();
+        while i < outputs {
+            let to_maker = match output_type(i) {
+                Output::Coin => output_asset_to(i).unwrap() == maker.value,
+                Output::Change => output_asset_to(i).unwrap() == maker.value,
+                _ => true,
+            };
+            require(to_maker, Errors::InvalidCancel);
+            i += 1;
+        }
+
+        log(CancelOrderEvent {
+            market_id,
+            predicate_root,
+            maker,
+            base_asset: market.base_asset,
+            quote_asset: market.quote_asset,
+            base_amount,
+            quote_amount,
+        });
+    }
+
+    // By the OWNER, returns the new market's id. Markets can't be changed or delisted, orders
+    // and indexers rely on the terms they were created under.
+    #[storage(read, write)]
+    fn list_market(
+        base_asset: AssetId,
+        quote_asset: AssetId,
+        base_asset_decimals: u32,
+        quote_asset_decimals: u32,
+        tick_size: u64,
+        lot_size: u64,
+    ) -> u64 {
+        require(
+            msg_sender().unwrap() == Identity::Address(OWNER),
+            Errors::Unauthorized,
+        );
+        require(
+            base_asset != quote_asset && tick_size > 0 && lot_size > 0,
+            Errors::InvalidMarket,
+        );
+        let market = MarketInfo {
+            base_asset,
+            quote_asset,
+            base_asset_decimals,
+            quote_asset_decimals,
+            price_decimals: PRICE_DECIMALS,
+            tick_size,
+            lot_size,
+        };
+        let market_id = storage.markets.len();
+        storage.markets.push(market);
+        log(MarketListedEvent {
+            market_id,
+            market,
+        });
+        market_id
+    }
+
+    #[storage(read)]
+    fn market_count() -> u64 {
+        storage.markets.len()
+    }
+
+    #[storage(read)]
+    fn market_info(market_id: u64) -> MarketInfo {
+        read_market(market_id)
+    }
+
+    // The root the proxy hashes for the template as uploaded, i.e. with its default configurables
+    #[storage(read)]
+    fn template_root(order_type: OrderType) -> Address {
+        let template = match order_type {
+            OrderType::BUY => storage.buy_predicate.read_slice(),
+            OrderType::SELL => storage.sell_predicate.read_slice(),
+        };
+        require(template.is_some(), Errors::InvalidTemplate);
+        bytecode_root(template.unwrap())
+    }
+
+    // Once per order type, by the OWNER, right after deployment
+    #[storage(read, write)]
+    fn set_predicate_template(order_type: OrderType, bytecode: Bytes, offsets: PredicateOffsets) {
+        check_template_upload(bytecode);
+        match order_type {
+            OrderType::BUY => {
+                require(storage.buy_offsets.read().is_none(), Errors::InvalidTemplate);
+                storage.buy_predicate.write_slice(bytecode);
+                storage.buy_offsets.write(Option::Some(offsets));
+            },
+            OrderType::SELL => {
+                require(storage.sell_offsets.read().is_none(), Errors::InvalidTemplate);
+                storage.sell_predicate.write_slice(bytecode);
+                storage.sell_offsets.write(Option::Some(offsets));
+            },
+        }
+    }
+
+    // The next three are set once each, like set_predicate_template
+    #[storage(read, write)]
+    fn set_auction_template(bytecode: Bytes, offsets: AuctionOffsets) {
+        check_template_upload(bytecode);
+        require(storage.auction_offsets.read().is_none(), Errors::InvalidTemplate);
+        storage.auction_predicate.write_slice(bytecode);
+        storage.auction_offsets.write(Option::Some(offsets));
+    }
+
+    #[storage(read, write)]
+    fn set_oco_template(bytecode: Bytes, offsets: OcoOffsets) {
+        check_template_upload(bytecode);
+        require(storage.oco_offsets.read().is_none(), Errors::InvalidTemplate);
+        storage.oco_predicate.write_slice(bytecode);
+        storage.oco_offsets.write(Option::Some(offsets));
+    }
+
+    #[storage(read, write)]
+    fn set_ladder_template(bytecode: Bytes, offsets: LadderOffsets) {
+        check_template_upload(bytecode);
+        require(storage.ladder_offsets.read().is_none(), Errors::InvalidTemplate);
+        storage.ladder_predicate.write_slice(bytecode);
+        storage.ladder_offsets.write(Option::Some(offsets));
+    }
+}
+
+fn check_template_upload(bytecode: Bytes) {
+    require(
+        msg_sender().unwrap() == Identity::Address(OWNER),
+        Errors::Unauthorized,
+    );
+    require(
+        bytecode.len() > 0 && bytecode.len() <= MAX_TEMPLATE_SIZE,
+        Errors::InvalidTemplate,
+    );
+}
+
+// The root of predicate-buy or predicate-sell configured the way `event` says, which the order
+// only is if it matches `event.predicate_root`. Predicates take an address as MAKER, so contract
+// makers never match.
+#[storage(read)]
+fn order_predicate_root(event: CreateOrderEvent) -> Address {
+    let (template, offsets) = match event.order_type {
+        OrderType::BUY => (storage.buy_predicate.read_slice(), storage.buy_offsets.read()),
+        OrderType::SELL => (storage.sell_predicate.read_slice(), storage.sell_offsets.read()),
+    };
+    require(
+        template.is_some() && offsets.is_some(),
+        Errors::InvalidTemplate,
+    );
+    let maker = event.maker.as_address();
+    require(maker.is_some(), Errors::PredicateRootMismatch);
+
+    let mut bytecode = template.unwrap();
+    let offsets = offsets.unwrap();
+    write_at(bytecode, offsets.base_asset, event.base_asset.value.to_be_bytes());
+    write_at(bytecode, offsets.quote_asset, event.quote_asset.value.to_be_bytes());
+    write_at(bytecode, offsets.maker, maker.unwrap().value.to_be_bytes());
+    write_at(bytecode, offsets.price, event.price.to_be_bytes());
+    write_at(bytecode, offsets.price_den, event.price_den.to_be_bytes());
+    write_at(bytecode, offsets.base_decimals, event.base_asset_decimals.as_u64().to_be_bytes());
+    write_at(bytecode, offsets.quote_decimals, event.quote_asset_decimals.as_u64().to_be_bytes());
+    write_at(bytecode, offsets.price_decimals, event.price_decimals.as_u64().to_be_bytes());
+    write_at(bytecode, offsets.min_fulfill_amount, event.min_fulfill_amount.to_be_bytes());
+    write_at(bytecode, offsets.expiry, event.expiry.to_be_bytes());
+    write_at(bytecode, offsets.expiry_script_hash, event.expiry_script_hash.to_be_bytes());
+    write_at(bytecode, offsets.nonce, event.nonce.to_be_bytes());
+    write_at(bytecode, offsets.fee_recipient, event.fee_recipient.value.to_be_bytes());
+    write_at(bytecode, offsets.fee_bps, event.fee_bps.to_be_bytes());
+    write_at(bytecode, offsets.recipient, event.recipient.value.to_be_bytes());
+    write_at(bytecode, offsets.allowed_taker, event.allowed_taker.value.to_be_bytes());
+    let all_or_none = if event.all_or_none { 1 } else { 0 };
+    write_at(bytecode, offsets.all_or_none, all_or_none.to_be_bytes());
+    bytecode_root(bytecode)
+}
+
+// The root of predicate-dutch-auction configured the way `event` says, in `market`
+#[storage(read)]
+fn auction_predicate_root(event: CreateAuctionEvent, market: MarketInfo) -> Address {
+    let template = storage.auction_predicate.read_slice();
+    let offsets = storage.auction_offsets.read();
+    require(
+        template.is_some() && offsets.is_some(),
+        Errors::InvalidTemplate,
+    );
+    let maker = event.maker.as_address();
+    require(maker.is_some(), Errors::PredicateRootMismatch);
+
+    let mut bytecode = template.unwrap();
+    let offsets = offsets.unwrap();
+    write_at(bytecode, offsets.base_asset, event.base_asset.value.to_be_bytes());
+    write_at(bytecode, offsets.quote_asset, event.quote_asset.value.to_be_bytes());
+    write_at(bytecode, offsets.maker, maker.unwrap().value.to_be_bytes());
+    write_at(bytecode, offsets.start_price, event.start_price.to_be_bytes());
+    write_at(bytecode, offsets.end_price, event.end_price.to_be_bytes());
+    write_at(bytecode, offsets.start_block, event.start_block.as_u64().to_be_bytes());
+    write_at(bytecode, offsets.end_block, event.end_block.as_u64().to_be_bytes());
+    write_at(bytecode, offsets.base_decimals, market.base_asset_decimals.as_u64().to_be_bytes());
+    write_at(bytecode, offsets.quote_decimals, market.quote_asset_decimals.as_u64().to_be_bytes());
+    write_at(bytecode, offsets.price_decimals, market.price_decimals.as_u64().to_be_bytes());
+    write_at(bytecode, offsets.nonce, event.nonce.to_be_bytes());
+    bytecode_root(bytecode)
+}
+
+// The root of predicate-oco configured the way `event` says, in `market`
+#[storage(read)]
+fn oco_predicate_root(event: CreateOcoOrderEvent, market: MarketInfo) -> Address {
+    let template = storage.oco_predicate.read_slice();
+    let offsets = storage.oco_offsets.read();
+    require(
+        template.is_some() && offsets.is_some(),
+        Errors::InvalidTemplate,
+    );
+    let maker = event.maker.as_address();
+    require(maker.is_some(), Errors::PredicateRootMismatch);
+
+    let mut bytecode = template.unwrap();
+    let offsets = offsets.unwrap();
+    write_at(bytecode, offsets.base_asset, event.base_asset.value.to_be_bytes());
+    write_at(bytecode, offsets.quote_asset, event.quote_asset.value.to_be_bytes());
+    write_at(bytecode, offsets.maker, maker.unwrap().value.to_be_bytes());
+    write_at(bytecode, offsets.take_profit_price, event.take_profit_price.to_be_bytes());
+    write_at(bytecode, offsets.stop_price, event.stop_price.to_be_bytes());
+    write_at(bytecode, offsets.stop_keeper, event.stop_keeper.value.to_be_bytes());
+    write_at(bytecode, offsets.base_decimals, market.base_asset_decimals.as_u64().to_be_bytes());
+    write_at(bytecode, offsets.quote_decimals, market.quote_asset_decimals.as_u64().to_be_bytes());
+    write_at(bytecode, offsets.price_decimals, market.price_decimals.as_u64().to_be_bytes());
+    write_at(bytecode, offsets.nonce, event.nonce.to_be_bytes());
+    bytecode_root(bytecode)
+}
+
+// The root of predicate-ladder configured the way `event` says, in `market`
+#[storage(read)]
+fn ladder_predicate_root(event: CreateLadderOrderEvent, market: MarketInfo) -> Address {
+    let template = storage.ladder_predicate.read_slice();
+    let offsets = storage.ladder_offsets.read();
+    require(
+        template.is_some() && offsets.is_some(),
+        Errors::InvalidTemplate,
+    );
+    let maker = event.maker.as_address();
+    require(maker.is_some(), Errors::PredicateRootMismatch);
+
+    let mut bytecode = template.unwrap();
+    let offsets = offsets.unwrap();
+    write_at(bytecode, offsets.base_asset, event.base_asset.value.to_be_bytes());
+    write_at(bytecode, offsets.quote_asset, event.quote_asset.value.to_be_bytes());
+    write_at(bytecode, offsets.maker, maker.unwrap().value.to_be_bytes());
+    write_words(bytecode, offsets.prices, event.prices);
+    write_words(bytecode, offsets.sizes, event.sizes);
+    write_at(bytecode, offsets.levels, event.levels.to_be_bytes());
+    write_at(bytecode, offsets.base_decimals, market.base_asset_decimals.as_u64().to_be_bytes());
+    write_at(bytecode, offsets.quote_decimals, market.quote_asset_decimals.as_u64().to_be_bytes());
+    write_at(bytecode, offsets.price_decimals, market.price_decimals.as_u64().to_be_bytes());
+    write_at(bytecode, offsets.nonce, event.nonce.to_be_bytes());
+    bytecode_root(bytecode)
+}
+
+#[storage(read)]
+fn read_market(market_id: u64) -> MarketInfo {
+    let market = storage.markets.get(market_id);
+    require(market.is_some(), Errors::InvalidMarket);
+    market.unwrap().read()
+}
+
+// sha256(predicate_root ++ maker ++ price ++ base_asset ++ quote_asset ++ nonce ++ amount), u64s
+// big-endian, the maker's address or contract id. `spark_sdk::order::order_id` is the same.
+fn compute_order_id(
+    market: MarketInfo,
+    predicate_root: Address,
+    maker: Identity,
+    price: u64,
+    nonce: b256,
+    amount: u64,
+) -> b256 {
+    let maker = match maker {
+        Identity::Address(address) => address.value,
+        Identity::ContractId(contract_id) => contract_id.value,
+    };
+    let mut hasher = Hasher::new();
+    predicate_root.value.hash(hasher);
+    maker.hash(hasher);
+    price.hash(hasher);
+    market.base_asset.value.hash(hasher);
+    market.quote_asset.value.hash(hasher);
+    nonce.hash(hasher);
+    amount.hash(hasher);
+    hasher.sha256()
 }
 
 // fn base_to_quote_amount(amount: u64, price: u64) -> u64 {
@@ -97,10 +952,29 @@ impl ProxyContract for Contract {
 // }
 
 
-fn quote_to_base_amount(amount: u64, price: u64) -> u64 {
-    amount.mul_div(
-        10_u64
-            .pow(BASE_ASSET_DECIMALS + PRICE_DECIMALS - QUOTE_ASSET_DECIMALS),
-        price,
-    )
+fn quote_to_base_amount(market: MarketInfo, amount: u64, price: u64, price_den: u64) -> u64 {
+    let (num, den) = price_fraction(market, price, price_den);
+    let base = U256::from((0, 0, 0, amount)) * den / num;
+    // a tiny price can ask for more base than fits in a coin
+    require(
+        base <= U256::from((0, 0, 0, u64::max())),
+        Errors::InvalidPrice,
+    );
+    base.as_u64().unwrap()
+}
+
+// price / price_den quote units per base unit, or price scaled down by
+// 10^(base_asset_decimals + price_decimals - quote_asset_decimals) when price_den is 0. The
+// exponent goes negative when the quote asset has more decimals.
+fn price_fraction(market: MarketInfo, price: u64, price_den: u64) -> (U256, U256) {
+    let price = U256::from((0, 0, 0, price));
+    if price_den != 0 {
+        return (price, U256::from((0, 0, 0, price_den)));
+    }
+    let up = market.base_asset_decimals + market.price_decimals;
+    if up >= market.quote_asset_decimals {
+        (price, pow10(up - market.quote_asset_decimals))
+    } else {
+        (price * pow10(market.quote_asset_decimals - up), U256::from((0, 0, 0, 1)))
+    }
 }
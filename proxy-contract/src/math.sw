@@ -1,16 +1,16 @@
 library;
-use std::u128::U128;
+use std::u256::U256;
 
 impl u64 {
     pub fn mul_div(self, mul_to: u64, div_to: u64) -> u64 {
-        let mul_result = U128::from((0, self)) * U128::from((0, mul_to));
-        let div_result = mul_result / U128::from((0, div_to));
+        let mul_result = U256::from((0, 0, 0, self)) * U256::from((0, 0, 0, mul_to));
+        let div_result = mul_result / U256::from((0, 0, 0, div_to));
         div_result.as_u64().unwrap()
     }
 
     pub fn mul_div_rounding_up(self, mul_to: u64, div_to: u64) -> u64{
-        let mul_result = U128::from((0, self)) * U128::from((0, mul_to));
-        let div_to = U128::from((0, div_to));
+        let mul_result = U256::from((0, 0, 0, self)) * U256::from((0, 0, 0, mul_to));
+        let div_to = U256::from((0, 0, 0, div_to));
         let div_result = mul_result / div_to;
         let add = if div_result * div_to < mul_result {1} else {0};
         div_result.as_u64().unwrap() + add
@@ -25,3 +25,14 @@ pub fn max(a: u64, b: u64) -> u64 {
 pub fn min(a: u64, b: u64) -> u64 {
     if a < b { a } else { b }
 }
+
+pub fn pow10(exp: u32) -> U256 {
+    let ten = U256::from((0, 0, 0, 10));
+    let mut result = U256::from((0, 0, 0, 1));
+    let mut i = 0;
+    while i < exp {
+        result = result * ten;
+        i += 1;
+    }
+    result
+}
@@ -0,0 +1,114 @@
+library;
+
+use std::bytes::Bytes;
+use std::bytes_conversions::u64::*;
+use std::hash::{Hash, Hasher};
+
+// A predicate's address is sha256("FUEL" ++ code root), the code root being the merkle root of
+// its bytecode split into 16 KiB leaves. Templates are kept to one leaf, whose root is
+// sha256(0x00 ++ bytecode padded to whole words).
+pub const MAX_TEMPLATE_SIZE: u64 = 16384;
+
+// Where predicate-buy or predicate-sell keep each configurable in their bytecode, the `offset`s
+// of their ABI. min_fulfill_amount is MIN_FULFILL_QUOTE_AMOUNT or MIN_FULFILL_BASE_AMOUNT.
+pub struct PredicateOffsets {
+    base_asset: u64,
+    quote_asset: u64,
+    maker: u64,
+    price: u64,
+    price_den: u64,
+    base_decimals: u64,
+    quote_decimals: u64,
+    price_decimals: u64,
+    min_fulfill_amount: u64,
+    expiry: u64,
+    expiry_script_hash: u64,
+    nonce: u64,
+    fee_recipient: u64,
+    fee_bps: u64,
+    recipient: u64,
+    allowed_taker: u64,
+    all_or_none: u64,
+}
+
+// The same for predicate-dutch-auction
+pub struct AuctionOffsets {
+    base_asset: u64,
+    quote_asset: u64,
+    maker: u64,
+    start_price: u64,
+    end_price: u64,
+    start_block: u64,
+    end_block: u64,
+    base_decimals: u64,
+    quote_decimals: u64,
+    price_decimals: u64,
+    nonce: u64,
+}
+
+// The same for predicate-oco
+pub struct OcoOffsets {
+    base_asset: u64,
+    quote_asset: u64,
+    maker: u64,
+    take_profit_price: u64,
+    stop_price: u64,
+    stop_keeper: u64,
+    base_decimals: u64,
+    quote_decimals: u64,
+    price_decimals: u64,
+    nonce: u64,
+}
+
+// The same for predicate-ladder, prices and sizes are 5 words each
+pub struct LadderOffsets {
+    base_asset: u64,
+    quote_asset: u64,
+    maker: u64,
+    prices: u64,
+    sizes: u64,
+    levels: u64,
+    base_decimals: u64,
+    quote_decimals: u64,
+    price_decimals: u64,
+    nonce: u64,
+}
+
+// Overwrites the encoded configurable at `offset`, words and bools take 8 bytes, b256s 32
+pub fn write_at(ref mut bytecode: Bytes, offset: u64, value: Bytes) {
+    let mut i = 0;
+    while i < value.len() {
+        bytecode.set(offset + i, value.get(i).unwrap());
+        i += 1;
+    }
+}
+
+// Writes an array configurable, one word per entry
+pub fn write_words(ref mut bytecode: Bytes, offset: u64, values: [u64; 5]) {
+    let mut i = 0;
+    while i < 5 {
+        write_at(bytecode, offset + i * 8, values[i].to_be_bytes());
+        i += 1;
+    }
+}
+
+pub fn bytecode_root(bytecode: Bytes) -> Address {
+    let mut hasher = Hasher::new();
+    0u8.hash(hasher);
+    bytecode.hash(hasher);
+    let mut len = bytecode.len();
+    while len % 8 != 0 {
+        0u8.hash(hasher);
+        len += 1;
+    }
+    let code_root = hasher.sha256();
+
+    // "FUEL", the seed fuel-tx prepends to predicate and contract roots
+    let mut hasher = Hasher::new();
+    0x46u8.hash(hasher);
+    0x55u8.hash(hasher);
+    0x45u8.hash(hasher);
+    0x4Cu8.hash(hasher);
+    code_root.hash(hasher);
+    Address::from(hasher.sha256())
+}
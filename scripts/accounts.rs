@@ -0,0 +1,89 @@
+use clap::{Parser, Subcommand};
+use fuels::{accounts::wallet::WalletUnlocked, types::Address};
+use spark_sdk::{
+    print_title,
+    wallet::{AccountSource, Accounts, KEYSTORE_PASSWORD_ENV},
+};
+use std::path::PathBuf;
+
+#[derive(Parser)]
+#[command(about = "Manage the named accounts used by the Spark scripts")]
+struct Cli {
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Print every account with its address
+    List,
+    /// Generate a new key, encrypt it with SPARK_KEYSTORE_PASSWORD and register it
+    NewKeystore {
+        name: String,
+        #[arg(long, default_value = "keystore")]
+        dir: PathBuf,
+    },
+    /// Register a mnemonic subaccount, the phrase itself stays in `phrase_env`
+    AddMnemonic {
+        name: String,
+        #[arg(long)]
+        phrase_env: String,
+        #[arg(long, default_value_t = 0)]
+        index: u32,
+        #[arg(long)]
+        derivation_path: Option<String>,
+    },
+}
+
+fn main() {
+    dotenv::dotenv().ok();
+    let cli = Cli::parse();
+    print_title("Accounts");
+
+    let path = Accounts::path_from_env();
+    let mut accounts = Accounts::from_env().unwrap();
+
+    match cli.command {
+        Command::List => {
+            // Addresses only need the keys, listing works offline
+            for name in accounts.names() {
+                match accounts.source(name).and_then(|source| source.unlock(None)) {
+                    Ok(wallet) => println!("{name} = 0x{}", Address::from(wallet.address())),
+                    Err(e) => println!("{name} = {e}"),
+                }
+            }
+        }
+        Command::NewKeystore { name, dir } => {
+            let password = std::env::var(KEYSTORE_PASSWORD_ENV).unwrap();
+            std::fs::create_dir_all(&dir).unwrap();
+            let (wallet, uuid) =
+                WalletUnlocked::new_from_keystore(&dir, &mut rand::thread_rng(), password, None)
+                    .unwrap();
+            accounts.insert(
+                &name,
+                AccountSource::Keystore {
+                    path: dir.join(uuid),
+                    password_env: None,
+                },
+            );
+            accounts.save(&path).unwrap();
+            println!("{name} = 0x{}", Address::from(wallet.address()));
+        }
+        Command::AddMnemonic {
+            name,
+            phrase_env,
+            index,
+            derivation_path,
+        } => {
+            let source = AccountSource::Mnemonic {
+                phrase_env,
+                derivation_path,
+                index,
+            };
+            let wallet = source.unlock(None).unwrap();
+            accounts.insert(&name, source);
+            accounts.save(&path).unwrap();
+            println!("{name} = 0x{}", Address::from(wallet.address()));
+        }
+    }
+}
@@ -1,16 +1,10 @@
-use std::{env, str::FromStr};
-
 use dotenv::dotenv;
-use fuels::{
-    accounts::wallet::WalletUnlocked,
-    prelude::Provider,
-    types::{Address, ContractId},
-};
+use fuels::types::Address;
 use spark_sdk::{
-    constants::{RPC, TOKEN_CONTRACT_ID},
+    network::NetworkProfile,
     spark_utils::Spark,
     print_title,
-    utils::get_contract_addresses,
+    wallet::Accounts,
 };
 use src20_sdk::token_utils::Asset;
 
@@ -27,18 +21,18 @@ async fn main() {
     dotenv().ok();
 
     //--------------- WALLETS ---------------
-    let provider = Provider::connect(RPC).await.unwrap();
+    let profile = NetworkProfile::from_env().unwrap();
+    let provider = profile.connect().await.unwrap();
 
-    let admin_pk = env::var("ADMIN").unwrap().parse().unwrap();
-    let admin = WalletUnlocked::new_from_private_key(admin_pk, Some(provider.clone()));
+    let accounts = Accounts::from_env().unwrap();
+    let admin = accounts.wallet_for_role("admin", &provider).unwrap();
 
-    let maker_pk = env::var("ALICE").unwrap().parse().unwrap();
-    let maker = WalletUnlocked::new_from_private_key(maker_pk, Some(provider.clone()));
+    let maker = accounts.wallet_for_role("maker", &provider).unwrap();
     let maker_address = Address::from(maker.address());
 
     println!("maker address = 0x{:?}\n", maker_address);
     //--------------- TOKENS ---------------
-    let token_contract_id = ContractId::from_str(TOKEN_CONTRACT_ID).unwrap().into();
+    let token_contract_id = profile.token_contract_id().unwrap().into();
     let quote_asset = Asset::new(admin.clone(), token_contract_id, QUOTE_ASSET);
     let base_asset = Asset::new(admin.clone(), token_contract_id, BASE_ASSET);
 
@@ -55,10 +49,11 @@ async fn main() {
     quote_asset.mint(maker_address, quote_amount).await.unwrap();
 
     //--------------- PREDICATE ---------
-    let contracts = get_contract_addresses();
-    let spark = Spark::new(&admin, &contracts.proxy).await;
+    let spark = Spark::from_profile(&admin, &profile, BASE_ASSET, QUOTE_ASSET)
+        .await
+        .unwrap();
 
-    let buy_predicate = spark.get_buy_predicate(&maker, &base_asset, &quote_asset, price, 1);
+    let buy_predicate = spark.get_buy_predicate(&maker, price, 1);
 
     let res = spark
         .with_account(&maker)
@@ -67,6 +62,7 @@ async fn main() {
             quote_asset.asset_id,
             quote_amount,
             price,
+            1,
         )
         .await
         .unwrap();
@@ -1,36 +1,31 @@
-use fuels::{
-    prelude::{Provider, WalletUnlocked},
-    types::ContractId,
-};
+use fuels::types::{Address, Bytes32};
+use rand::Rng;
 use spark_sdk::{
-    constants::{RPC, TOKEN_CONTRACT_ID},
-    spark_utils::Spark,
+    manifest::{AssetDeployment, DeploymentManifest, MarketDeployment},
+    network::NetworkProfile,
     print_title,
-    utils::{set_contract_addresses, ContractAddresses},
+    spark_utils::{Spark, PRICE_DECIMALS},
+    wallet::Accounts,
 };
 use src20_sdk::token_utils::{Asset, TokenContract};
-use std::str::FromStr;
 
 const BASE_ASSET: &str = "BTC";
 const QUOTE_ASSET: &str = "USDC";
 
-// 🏁 Start_block: 11266711
-
 #[tokio::main]
 async fn main() {
     dotenv::dotenv().ok();
 
     print_title("✨ Deploy proxy ✨ ");
 
-    let provider = Provider::connect(RPC).await.unwrap();
+    let profile = NetworkProfile::from_env().unwrap();
+    let provider = profile.connect().await.unwrap();
 
-    let admin_pk = std::env::var("ADMIN").unwrap().parse().unwrap();
-    let admin = &WalletUnlocked::new_from_private_key(admin_pk, Some(provider.clone()));
+    let accounts = Accounts::from_env().unwrap();
+    let admin = &accounts.wallet_for_role("admin", &provider).unwrap();
 
-    let token_contract = TokenContract::new(
-        &ContractId::from_str(TOKEN_CONTRACT_ID).unwrap().into(),
-        admin.clone(),
-    );
+    let token_contract_id = profile.token_contract_id().unwrap();
+    let token_contract = TokenContract::new(&token_contract_id.into(), admin.clone());
     let base_asset = Asset::new(
         admin.clone(),
         token_contract.contract_id().into(),
@@ -44,13 +39,39 @@ async fn main() {
 
     let block = provider.latest_block_height().await.unwrap();
     println!("🏁 Start_block: {block}\n");
-    let spark = Spark::deploy_proxy(admin, &base_asset, &quote_asset).await;
+    let salt = rand::thread_rng().gen::<[u8; 32]>();
+    let spark = Spark::deploy_proxy_with_salt(admin, &base_asset, &quote_asset, salt).await;
 
+    println!("Network = {:?} ({})", profile.name, profile.rpc);
     println!("Market = {:?} / {:?}", BASE_ASSET, QUOTE_ASSET);
     println!("proxy = 0x{:?}", spark.proxy.contract_id().hash);
     println!("proxy = {:?}\n", spark.proxy.contract_id().to_string());
 
-    set_contract_addresses(ContractAddresses {
-        proxy: spark.proxy.contract_id().hash.to_string(),
-    });
+    let deployment = MarketDeployment {
+        proxy: format!("0x{}", spark.proxy.contract_id().hash),
+        base_asset: AssetDeployment {
+            symbol: BASE_ASSET.to_string(),
+            asset_id: format!("0x{}", base_asset.asset_id),
+            decimals: base_asset.decimals as u32,
+        },
+        quote_asset: AssetDeployment {
+            symbol: QUOTE_ASSET.to_string(),
+            asset_id: format!("0x{}", quote_asset.asset_id),
+            decimals: quote_asset.decimals as u32,
+        },
+        price_decimals: PRICE_DECIMALS,
+        buy_predicate_hash: Spark::buy_predicate_hash(),
+        sell_predicate_hash: Spark::sell_predicate_hash(),
+        deployer: format!("0x{}", Address::from(admin.address())),
+        salt: format!("0x{}", Bytes32::from(salt)),
+        tx_id: None,
+        start_block: block,
+    };
+
+    // SPARK_MANIFEST or ./deployments.json
+    let path = DeploymentManifest::path_from_env();
+    let mut manifest = DeploymentManifest::load_or_default(&path).unwrap();
+    manifest.insert(&profile.name, deployment).unwrap();
+    manifest.save(&path).unwrap();
+    println!("Saved to {}", path.display());
 }
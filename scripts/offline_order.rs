@@ -0,0 +1,417 @@
+use clap::{Args, Parser, Subcommand, ValueEnum};
+use fuels::{
+    accounts::{predicate::Predicate, wallet::WalletUnlocked, ViewOnlyAccount},
+    prelude::{Bech32Address, Provider},
+    types::{transaction::ScriptTransaction, Address, Bits256, Bytes32},
+};
+use spark_sdk::{
+    network::NetworkProfile,
+    offline::{CoinTransfer, OfflineAction, OfflineFormat, OfflineOutcome, OfflineTx},
+    order::{Expiry, OrderFee, OrderOptions},
+    print_title,
+    signer::{SignerAccount, WatchOnlySigner},
+    spark_utils::Spark,
+    wallet::{role_account, Accounts},
+};
+use src20_sdk::token_utils::Asset;
+use std::{path::PathBuf, str::FromStr};
+
+const BASE_ASSET: &str = "BTC";
+const QUOTE_ASSET: &str = "USDC";
+
+#[derive(Parser)]
+#[command(about = "Build orders on an online machine, sign them offline, submit them back")]
+struct Cli {
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Clone, Copy, ValueEnum)]
+enum Side {
+    Buy,
+    Sell,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Build an unsigned create_order tx funding `maker`'s predicate with `amount`
+    BuildCreate {
+        #[arg(long)]
+        maker: String,
+        #[arg(long, value_enum)]
+        side: Side,
+        #[arg(long)]
+        price: u64,
+        #[arg(long)]
+        amount: u64,
+        #[command(flatten)]
+        terms: OrderTerms,
+        #[arg(long)]
+        out: PathBuf,
+        #[arg(long)]
+        binary: bool,
+    },
+    /// Build an unsigned tx taking `amount` back out of `maker`'s predicate
+    BuildCancel {
+        #[arg(long)]
+        maker: String,
+        #[arg(long, value_enum)]
+        side: Side,
+        #[arg(long)]
+        price: u64,
+        #[arg(long)]
+        amount: u64,
+        #[command(flatten)]
+        terms: OrderTerms,
+        #[arg(long)]
+        out: PathBuf,
+        #[arg(long)]
+        binary: bool,
+    },
+    /// Build an unsigned tx where `taker` takes `amount` from the order and pays `pay`
+    BuildFill {
+        #[arg(long)]
+        taker: String,
+        #[arg(long)]
+        maker: String,
+        #[arg(long, value_enum)]
+        side: Side,
+        #[arg(long)]
+        price: u64,
+        #[arg(long)]
+        amount: u64,
+        #[arg(long)]
+        pay: u64,
+        #[command(flatten)]
+        terms: OrderTerms,
+        #[arg(long)]
+        out: PathBuf,
+        #[arg(long)]
+        binary: bool,
+    },
+    /// Sign an artifact with a named account, no node needed
+    Sign {
+        input: PathBuf,
+        #[arg(long)]
+        account: Option<String>,
+        #[arg(long)]
+        out: Option<PathBuf>,
+        #[arg(long)]
+        binary: bool,
+    },
+    /// Broadcast a signed artifact and print the outcome
+    Submit { input: PathBuf },
+}
+
+// Everything besides price and assets that goes into the predicate, has to match the order
+#[derive(Args)]
+struct OrderTerms {
+    /// EXPIRY the order was made with: block height, TAI64 timestamp or 0
+    #[arg(long, default_value_t = 0)]
+    expiry: u64,
+    /// NONCE the order was made with, hex
+    #[arg(long)]
+    nonce: Option<String>,
+    /// FEE_RECIPIENT the order was made with
+    #[arg(long, requires = "fee_bps")]
+    fee_recipient: Option<String>,
+    /// FEE_BPS the order was made with
+    #[arg(long, requires = "fee_recipient")]
+    fee_bps: Option<u64>,
+    /// RECIPIENT the order was made with, when the proceeds don't go to the maker
+    #[arg(long)]
+    recipient: Option<String>,
+    /// ALLOWED_TAKER of a private order
+    #[arg(long)]
+    allowed_taker: Option<String>,
+    /// The order was made all-or-none
+    #[arg(long)]
+    all_or_none: bool,
+}
+
+impl OrderTerms {
+    fn options(&self) -> OrderOptions {
+        let mut options =
+            OrderOptions::default().with_expiry(Expiry::from_configurable(self.expiry));
+        if let Some(nonce) = &self.nonce {
+            options = options.with_nonce(Bits256::from_hex_str(nonce).unwrap());
+        }
+        if let (Some(recipient), Some(bps)) = (&self.fee_recipient, self.fee_bps) {
+            options = options.with_fee(OrderFee::new(Address::from_str(recipient).unwrap(), bps));
+        }
+        if let Some(recipient) = &self.recipient {
+            options = options.with_recipient(Address::from_str(recipient).unwrap());
+        }
+        if let Some(taker) = &self.allowed_taker {
+            options = options.with_allowed_taker(Address::from_str(taker).unwrap());
+        }
+        if self.all_or_none {
+            options = options.with_all_or_none();
+        }
+        options
+    }
+}
+
+#[tokio::main]
+async fn main() {
+    dotenv::dotenv().ok();
+    let cli = Cli::parse();
+    print_title("Offline order");
+
+    match cli.command {
+        Command::BuildCreate {
+            maker,
+            side,
+            price,
+            amount,
+            terms,
+            out,
+            binary,
+        } => {
+            let market = Market::connect().await;
+            // A new order gets a nonce of its own unless one is given
+            let options = match terms.nonce {
+                Some(_) => terms.options(),
+                None => terms.options().with_random_nonce(),
+            };
+            println!("nonce = {}", Bytes32::from(options.nonce.0));
+            let maker = market.watch_only(&maker);
+            let predicate = market.predicate(maker.address(), side, price, &options);
+            let tx = market
+                .spark
+                .with_account(&maker)
+                .build_create_order(
+                    predicate.address().into(),
+                    market.payment_asset(side).asset_id,
+                    amount,
+                    price,
+                    &options,
+                )
+                .await
+                .unwrap();
+            let offline_tx = market.offline_tx(OfflineAction::CreateOrder, tx, &maker, &predicate);
+            save(&offline_tx, &out, binary);
+        }
+        Command::BuildCancel {
+            maker,
+            side,
+            price,
+            amount,
+            terms,
+            out,
+            binary,
+        } => {
+            let market = Market::connect().await;
+            let options = terms.options();
+            let maker = market.watch_only(&maker);
+            let predicate = market.predicate(maker.address(), side, price, &options);
+            let tx = market
+                .spark
+                .build_cancel_order(
+                    &maker,
+                    &predicate,
+                    market.payment_asset(side).asset_id,
+                    amount,
+                )
+                .await
+                .unwrap();
+            let offline_tx = market.offline_tx(OfflineAction::CancelOrder, tx, &maker, &predicate);
+            save(&offline_tx, &out, binary);
+        }
+        Command::BuildFill {
+            taker,
+            maker,
+            side,
+            price,
+            amount,
+            pay,
+            terms,
+            out,
+            binary,
+        } => {
+            let market = Market::connect().await;
+            let options = terms.options();
+            let taker = market.watch_only(&taker);
+            let maker = parse_address(&maker);
+            let predicate = market.predicate(&maker, side, price, &options);
+            let tx = market
+                .spark
+                .build_fulfill_order(
+                    &taker,
+                    &predicate,
+                    &maker,
+                    market.payment_asset(side).asset_id,
+                    amount,
+                    market.asked_asset(side).asset_id,
+                    pay,
+                    &options,
+                )
+                .await
+                .unwrap();
+            let offline_tx = market.offline_tx(OfflineAction::FulfillOrder, tx, &taker, &predicate);
+            save(&offline_tx, &out, binary);
+        }
+        Command::Sign {
+            input,
+            account,
+            out,
+            binary,
+        } => {
+            let mut offline_tx = OfflineTx::load(&input).unwrap();
+            println!("{:?} for {}", offline_tx.action, offline_tx.signer);
+            println!("predicate = {}", offline_tx.predicate_root);
+            for utxo in &offline_tx.utxos {
+                println!(
+                    "spends {} of {} from {} ({})",
+                    utxo.amount, utxo.asset_id, utxo.owner, utxo.utxo_id
+                );
+            }
+
+            let account = account.unwrap_or_else(|| role_account("maker"));
+            let signer = Accounts::from_env().unwrap().signer(&account).unwrap();
+            offline_tx.sign(&signer).await.unwrap();
+            save(&offline_tx, &out.unwrap_or(input), binary);
+        }
+        Command::Submit { input } => {
+            let offline_tx = OfflineTx::load(&input).unwrap();
+            let provider = NetworkProfile::from_env()
+                .unwrap()
+                .connect()
+                .await
+                .unwrap();
+            match offline_tx.submit(&provider).await.unwrap() {
+                OfflineOutcome::OrderCreated { tx_id, events } => {
+                    println!("Order created, tx = 0x{tx_id}");
+                    for event in events {
+                        println!("{event:#?}");
+                    }
+                }
+                OfflineOutcome::OrderFilled { tx_id, transfers } => {
+                    println!("Order filled, tx = 0x{tx_id}");
+                    print_transfers(&transfers);
+                }
+                OfflineOutcome::OrderCancelled {
+                    tx_id,
+                    transfers,
+                    events,
+                } => {
+                    println!("Order cancelled, tx = 0x{tx_id}");
+                    for event in events {
+                        println!("{event:#?}");
+                    }
+                    print_transfers(&transfers);
+                }
+            }
+        }
+    }
+}
+
+fn print_transfers(transfers: &[CoinTransfer]) {
+    for transfer in transfers {
+        println!(
+            "{} of 0x{} to 0x{}",
+            transfer.amount, transfer.asset_id, transfer.to
+        );
+    }
+}
+
+struct Market {
+    provider: Provider,
+    spark: Spark,
+    base_asset: Asset,
+    quote_asset: Asset,
+}
+
+impl Market {
+    // Building only needs addresses, the wallet behind `spark` never signs anything
+    async fn connect() -> Self {
+        let profile = NetworkProfile::from_env().unwrap();
+        let provider = profile.connect().await.unwrap();
+        let wallet = WalletUnlocked::new_random(Some(provider.clone()));
+        let token_contract_id = profile.token_contract_id().unwrap().into();
+        let base_asset = Asset::new(wallet.clone(), token_contract_id, BASE_ASSET);
+        let quote_asset = Asset::new(wallet.clone(), token_contract_id, QUOTE_ASSET);
+        let spark = Spark::from_profile(&wallet, &profile, BASE_ASSET, QUOTE_ASSET)
+            .await
+            .unwrap();
+        Self {
+            provider,
+            spark,
+            base_asset,
+            quote_asset,
+        }
+    }
+
+    fn watch_only(&self, address: &str) -> SignerAccount<WatchOnlySigner> {
+        SignerAccount::new(
+            WatchOnlySigner::new(parse_address(address)),
+            self.provider.clone(),
+        )
+    }
+
+    fn predicate(
+        &self,
+        maker: &Bech32Address,
+        side: Side,
+        price: u64,
+        options: &OrderOptions,
+    ) -> Predicate {
+        let reader = self.spark.reader();
+        let market = self.spark.market();
+        match side {
+            Side::Buy => reader.get_buy_predicate_with_options(maker, market, price, options),
+            Side::Sell => reader.get_sell_predicate_with_options(maker, market, price, options),
+        }
+    }
+
+    // what the predicate holds
+    fn payment_asset(&self, side: Side) -> &Asset {
+        match side {
+            Side::Buy => &self.quote_asset,
+            Side::Sell => &self.base_asset,
+        }
+    }
+
+    // what the maker wants for it
+    fn asked_asset(&self, side: Side) -> &Asset {
+        match side {
+            Side::Buy => &self.base_asset,
+            Side::Sell => &self.quote_asset,
+        }
+    }
+
+    fn offline_tx(
+        &self,
+        action: OfflineAction,
+        tx: ScriptTransaction,
+        signer: &SignerAccount<WatchOnlySigner>,
+        predicate: &Predicate,
+    ) -> OfflineTx {
+        OfflineTx::new(
+            action,
+            tx,
+            self.provider.chain_id(),
+            self.spark.proxy.contract_id(),
+            signer.address(),
+            predicate,
+        )
+    }
+}
+
+fn parse_address(address: &str) -> Bech32Address {
+    Address::from_str(address).unwrap().into()
+}
+
+fn save(offline_tx: &OfflineTx, path: &PathBuf, binary: bool) {
+    let format = if binary {
+        OfflineFormat::Binary
+    } else {
+        OfflineFormat::Json
+    };
+    offline_tx.save(path, format).unwrap();
+    println!(
+        "tx 0x{} saved to {}",
+        offline_tx.id().unwrap(),
+        path.display()
+    );
+}
@@ -1,16 +1,13 @@
-use std::{env, str::FromStr};
-
 use dotenv::dotenv;
 use fuels::{
-    accounts::{wallet::WalletUnlocked, ViewOnlyAccount},
-    prelude::Provider,
-    types::{Address, ContractId},
+    accounts::ViewOnlyAccount,
+    types::Address,
 };
 use spark_sdk::{
-    constants::{RPC, TOKEN_CONTRACT_ID},
+    network::NetworkProfile,
     print_title,
     spark_utils::{CreateOrderEvent, Spark},
-    utils::get_contract_addresses,
+    wallet::Accounts,
 };
 use src20_sdk::token_utils::Asset;
 
@@ -27,23 +24,22 @@ async fn main() {
     dotenv().ok();
 
     //--------------- WALLETS ---------------
-    let provider = Provider::connect(RPC).await.unwrap();
+    let profile = NetworkProfile::from_env().unwrap();
+    let provider = profile.connect().await.unwrap();
 
-    let admin_pk = env::var("ADMIN").unwrap().parse().unwrap();
-    let admin = WalletUnlocked::new_from_private_key(admin_pk, Some(provider.clone()));
+    let accounts = Accounts::from_env().unwrap();
+    let admin = accounts.wallet_for_role("admin", &provider).unwrap();
 
-    let maker_pk = env::var("ALICE").unwrap().parse().unwrap();
-    let maker = WalletUnlocked::new_from_private_key(maker_pk, Some(provider.clone()));
+    let maker = accounts.wallet_for_role("maker", &provider).unwrap();
     let maker_address = Address::from(maker.address());
 
-    let taker_pk = env::var("BOB").unwrap().parse().unwrap();
-    let taker = WalletUnlocked::new_from_private_key(taker_pk, Some(provider.clone()));
+    let taker = accounts.wallet_for_role("taker", &provider).unwrap();
     let taker_address = Address::from(taker.address());
 
     println!("maker address = 0x{:?}\n", maker_address);
     println!("taker address = 0x{:?}\n", taker_address);
     //--------------- TOKENS ---------------
-    let token_contract_id = ContractId::from_str(TOKEN_CONTRACT_ID).unwrap().into();
+    let token_contract_id = profile.token_contract_id().unwrap().into();
     let quote_asset = Asset::new(admin.clone(), token_contract_id, QUOTE_ASSET);
     let base_asset = Asset::new(admin.clone(), token_contract_id, BASE_ASSET);
 
@@ -59,9 +55,10 @@ async fn main() {
     base_asset.mint(taker_address, base_amount).await.unwrap();
 
     //--------------- PREDICATE ---------
-    let contracts = get_contract_addresses();
-    let spark = Spark::new(&admin, &contracts.proxy).await;
-    let buy_predicate = spark.get_buy_predicate(&maker, &base_asset, &quote_asset, price, 1);
+    let spark = Spark::from_profile(&admin, &profile, BASE_ASSET, QUOTE_ASSET)
+        .await
+        .unwrap();
+    let buy_predicate = spark.get_buy_predicate(&maker, price, 1);
     let root = buy_predicate.address();
 
     let initial_taker_btc_balance = taker.get_asset_balance(&base_asset.asset_id).await.unwrap();
@@ -77,7 +74,7 @@ async fn main() {
 
     let res = spark
         .with_account(&maker)
-        .create_order(root.into(), quote_asset.asset_id, quote_amount, price)
+        .create_order(root.into(), quote_asset.asset_id, quote_amount, price, 1)
         .await
         .unwrap();
 
@@ -0,0 +1,82 @@
+use crate::order::{decimals_scale, quote_amount_for, to_u64};
+use crate::spark_utils::PRICE_DECIMALS;
+use fuels::prelude::Provider;
+use primitive_types::U256;
+
+// Dutch auction terms, the asked price falls linearly from `start_price` at `start_block` to
+// `end_price` at `end_block` and stays there. Prices are quoted like order prices.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DutchAuction {
+    pub start_price: u64,
+    pub end_price: u64,
+    pub start_block: u32,
+    pub end_block: u32,
+}
+
+impl DutchAuction {
+    pub fn new(start_price: u64, end_price: u64, start_block: u32, end_block: u32) -> Self {
+        Self {
+            start_price,
+            end_price,
+            start_block,
+            end_block,
+        }
+    }
+
+    /// Auctions the predicate and the proxy accept: a price that never goes up and a window of at
+    /// least one block.
+    pub fn is_valid(&self) -> bool {
+        self.end_price > 0
+            && self.start_price >= self.end_price
+            && self.start_block < self.end_block
+    }
+
+    /// The price the predicate asks for a fill maturing at `height`, rounded like on-chain.
+    pub fn price_at(&self, height: u32) -> u64 {
+        if height <= self.start_block {
+            return self.start_price;
+        }
+        if height >= self.end_block {
+            return self.end_price;
+        }
+        let elapsed = (height - self.start_block) as u128;
+        let duration = (self.end_block - self.start_block) as u128;
+        let drop = (self.start_price - self.end_price) as u128 * elapsed / duration;
+        self.start_price - drop as u64
+    }
+
+    /// Quote the taker has to pay for `base_amount` at `height`, rounded up so the predicate's
+    /// check passes.
+    pub fn quote_amount_at(
+        &self,
+        base_amount: u64,
+        height: u32,
+        base_decimals: u32,
+        quote_decimals: u32,
+    ) -> u64 {
+        quote_amount_for(
+            base_amount,
+            self.price_at(height),
+            base_decimals,
+            quote_decimals,
+        )
+    }
+
+    /// Base `quote_amount` buys at `height`, rounded down.
+    pub fn base_amount_at(
+        &self,
+        quote_amount: u64,
+        height: u32,
+        base_decimals: u32,
+        quote_decimals: u32,
+    ) -> u64 {
+        let (up, down) = decimals_scale(PRICE_DECIMALS, base_decimals, quote_decimals);
+        to_u64(U256::from(quote_amount) * up / (U256::from(self.price_at(height)) * down))
+            .expect("base amount doesn't fit in a u64")
+    }
+
+    /// The price a fill maturing at the latest block pays.
+    pub async fn current_price(&self, provider: &Provider) -> Result<u64, fuels::prelude::Error> {
+        Ok(self.price_at(provider.latest_block_height().await?))
+    }
+}
@@ -1,4 +1,5 @@
-pub const RPC: &str = "beta-5.fuel.network";
+pub const TESTNET_RPC: &str = "beta-5.fuel.network";
+pub const LOCAL_RPC: &str = "127.0.0.1:4000";
 
 pub const TOKEN_CONTRACT_ID: &str =
     "0x6bd9643c9279204b474a778dea7f923226060cb94a4c61c5aae015cf96b5aad2";
@@ -0,0 +1,117 @@
+use crate::order::{decimals_scale, div_ceil, to_u64};
+use crate::spark_utils::PRICE_DECIMALS;
+use primitive_types::U256;
+
+// What predicate-ladder has room for
+pub const MAX_LADDER_LEVELS: usize = 5;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct LadderLevel {
+    pub price: u64,
+    // in the base asset
+    pub size: u64,
+}
+
+impl LadderLevel {
+    pub fn new(price: u64, size: u64) -> Self {
+        Self { price, size }
+    }
+}
+
+// A sell order in price levels, filled in order: the first level's size at its price, then
+// the next one's... Prices go up from level to level.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PriceLadder {
+    pub levels: Vec<LadderLevel>,
+}
+
+impl PriceLadder {
+    pub fn new(levels: Vec<LadderLevel>) -> Self {
+        Self { levels }
+    }
+
+    pub fn is_valid(&self) -> bool {
+        !self.levels.is_empty()
+            && self.levels.len() <= MAX_LADDER_LEVELS
+            && self
+                .levels
+                .iter()
+                .all(|level| level.price > 0 && level.size > 0)
+            && self
+                .levels
+                .windows(2)
+                .all(|pair| pair[1].price > pair[0].price)
+    }
+
+    /// Base asset the order is funded with, all levels together.
+    pub fn total_size(&self) -> u64 {
+        self.levels.iter().map(|level| level.size).sum()
+    }
+
+    /// The PRICES, SIZES and LEVELS configurables, unused levels are zeroed.
+    pub fn to_configurables(&self) -> ([u64; MAX_LADDER_LEVELS], [u64; MAX_LADDER_LEVELS], u64) {
+        let mut prices = [0; MAX_LADDER_LEVELS];
+        let mut sizes = [0; MAX_LADDER_LEVELS];
+        for (i, level) in self.levels.iter().take(MAX_LADDER_LEVELS).enumerate() {
+            prices[i] = level.price;
+            sizes[i] = level.size;
+        }
+        (prices, sizes, self.levels.len() as u64)
+    }
+
+    /// Reads a ladder back from `CreateLadderOrderEvent` fields.
+    pub fn from_configurables(
+        prices: [u64; MAX_LADDER_LEVELS],
+        sizes: [u64; MAX_LADDER_LEVELS],
+        levels: u64,
+    ) -> Self {
+        let levels = (0..(levels as usize).min(MAX_LADDER_LEVELS))
+            .map(|i| LadderLevel::new(prices[i], sizes[i]))
+            .collect();
+        Self { levels }
+    }
+
+    /// How much was sold when the predicate's coin holds `balance`.
+    pub fn consumed(&self, balance: u64) -> u64 {
+        self.total_size().saturating_sub(balance)
+    }
+
+    /// What's left on the book when the predicate's coin holds `balance`, level by level.
+    pub fn remaining_levels(&self, balance: u64) -> Vec<LadderLevel> {
+        let mut consumed = self.consumed(balance);
+        let mut remaining = vec![];
+        for level in &self.levels {
+            let taken = consumed.min(level.size);
+            consumed -= taken;
+            if level.size > taken {
+                remaining.push(LadderLevel::new(level.price, level.size - taken));
+            }
+        }
+        remaining
+    }
+
+    /// Quote a fill of `base_amount` has to pay when the predicate's coin holds `balance`, rounded up
+    /// like the predicate checks it. None if the ladder doesn't have that much left, or the
+    /// quote wouldn't fit in a u64.
+    pub fn quote_amount(
+        &self,
+        balance: u64,
+        base_amount: u64,
+        base_decimals: u32,
+        quote_decimals: u32,
+    ) -> Option<u64> {
+        let mut left = base_amount;
+        let mut value = U256::zero();
+        for level in self.remaining_levels(balance) {
+            let taken = left.min(level.size);
+            value += U256::from(taken) * U256::from(level.price);
+            left -= taken;
+        }
+        if left > 0 {
+            return None;
+        }
+        // the smallest quote with quote * 10^scale_exponent >= value
+        let (up, down) = decimals_scale(PRICE_DECIMALS, base_decimals, quote_decimals);
+        to_u64(div_ceil(value * down, up))
+    }
+}
@@ -1,6 +1,15 @@
+pub mod auction;
 pub mod constants;
+pub mod ladder;
+pub mod manifest;
+pub mod network;
+pub mod oco;
+pub mod offline;
+pub mod order;
+pub mod rfq;
+pub mod signer;
 pub mod spark_utils;
-pub mod utils;
+pub mod wallet;
 
 pub fn print_title(title: &str) {
     println!(
@@ -0,0 +1,295 @@
+use std::collections::BTreeMap;
+use std::env;
+use std::fmt;
+use std::path::{Path, PathBuf};
+
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+
+use crate::network::market_key;
+use crate::order::validate_decimals;
+
+pub const MANIFEST_ENV: &str = "SPARK_MANIFEST";
+pub const DEFAULT_MANIFEST_FILE: &str = "deployments.json";
+pub const MANIFEST_VERSION: u32 = 1;
+
+#[derive(Debug)]
+pub enum ManifestError {
+    Io(std::io::Error),
+    Json(serde_json::Error),
+    UnsupportedVersion(u32),
+    InvalidField {
+        network: String,
+        market: String,
+        field: &'static str,
+        reason: String,
+    },
+    Conflict {
+        network: String,
+        market: String,
+    },
+}
+
+impl fmt::Display for ManifestError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ManifestError::Io(e) => write!(f, "manifest io error: {e}"),
+            ManifestError::Json(e) => write!(f, "manifest json error: {e}"),
+            ManifestError::UnsupportedVersion(v) => {
+                write!(
+                    f,
+                    "unsupported manifest version {v}, expected {MANIFEST_VERSION}"
+                )
+            }
+            ManifestError::InvalidField {
+                network,
+                market,
+                field,
+                reason,
+            } => write!(f, "{network} {market}: invalid `{field}`: {reason}"),
+            ManifestError::Conflict { network, market } => {
+                write!(f, "{network} {market}: conflicting deployments")
+            }
+        }
+    }
+}
+
+impl std::error::Error for ManifestError {}
+
+impl From<std::io::Error> for ManifestError {
+    fn from(e: std::io::Error) -> Self {
+        ManifestError::Io(e)
+    }
+}
+
+impl From<serde_json::Error> for ManifestError {
+    fn from(e: serde_json::Error) -> Self {
+        ManifestError::Json(e)
+    }
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq, Eq)]
+pub struct AssetDeployment {
+    pub symbol: String,
+    pub asset_id: String,
+    pub decimals: u32,
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq, Eq)]
+pub struct MarketDeployment {
+    pub proxy: String,
+    pub base_asset: AssetDeployment,
+    pub quote_asset: AssetDeployment,
+    pub price_decimals: u32,
+    // sha256 of the predicate binaries the proxy was deployed with
+    pub buy_predicate_hash: String,
+    pub sell_predicate_hash: String,
+    pub deployer: String,
+    pub salt: String,
+    // `Contract::deploy` doesn't hand the create tx back, so it may be unknown
+    #[serde(default)]
+    pub tx_id: Option<String>,
+    pub start_block: u32,
+}
+
+impl MarketDeployment {
+    pub fn market(&self) -> String {
+        market_key(&self.base_asset.symbol, &self.quote_asset.symbol)
+    }
+
+    pub fn validate(&self, network: &str) -> Result<(), ManifestError> {
+        let invalid = |field: &'static str, reason: String| ManifestError::InvalidField {
+            network: network.to_string(),
+            market: self.market(),
+            field,
+            reason,
+        };
+
+        for (field, value) in [
+            ("proxy", &self.proxy),
+            ("base_asset.asset_id", &self.base_asset.asset_id),
+            ("quote_asset.asset_id", &self.quote_asset.asset_id),
+            ("buy_predicate_hash", &self.buy_predicate_hash),
+            ("sell_predicate_hash", &self.sell_predicate_hash),
+            ("deployer", &self.deployer),
+            ("salt", &self.salt),
+        ] {
+            check_b256(value).map_err(|reason| invalid(field, reason))?;
+        }
+        if let Some(tx_id) = &self.tx_id {
+            check_b256(tx_id).map_err(|reason| invalid("tx_id", reason))?;
+        }
+
+        if strip_0x(&self.base_asset.asset_id) == strip_0x(&self.quote_asset.asset_id) {
+            return Err(invalid(
+                "quote_asset.asset_id",
+                "base and quote assets are the same".to_string(),
+            ));
+        }
+        validate_decimals(
+            self.price_decimals,
+            self.base_asset.decimals,
+            self.quote_asset.decimals,
+        )
+        .map_err(|e| invalid("price_decimals", e.to_string()))?;
+        Ok(())
+    }
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq, Eq)]
+pub struct DeploymentManifest {
+    pub version: u32,
+    // network name => "BASE/QUOTE" => deployment
+    #[serde(default)]
+    pub networks: BTreeMap<String, BTreeMap<String, MarketDeployment>>,
+}
+
+impl Default for DeploymentManifest {
+    fn default() -> Self {
+        Self {
+            version: MANIFEST_VERSION,
+            networks: BTreeMap::new(),
+        }
+    }
+}
+
+impl DeploymentManifest {
+    pub fn path_from_env() -> PathBuf {
+        env::var(MANIFEST_ENV)
+            .map(PathBuf::from)
+            .unwrap_or(PathBuf::from(DEFAULT_MANIFEST_FILE))
+    }
+
+    /// Reads `SPARK_MANIFEST` or `./deployments.json`; a missing file is an empty manifest.
+    pub fn from_env() -> Result<Self, ManifestError> {
+        Self::load_or_default(Self::path_from_env())
+    }
+
+    pub fn load(path: impl AsRef<Path>) -> Result<Self, ManifestError> {
+        let json = std::fs::read_to_string(path)?;
+        let manifest: Self = serde_json::from_str(&json)?;
+        manifest.validate()?;
+        Ok(manifest)
+    }
+
+    pub fn load_or_default(path: impl AsRef<Path>) -> Result<Self, ManifestError> {
+        if path.as_ref().exists() {
+            Self::load(path)
+        } else {
+            Ok(Self::default())
+        }
+    }
+
+    pub fn save(&self, path: impl AsRef<Path>) -> Result<(), ManifestError> {
+        self.validate()?;
+        if let Some(dir) = path.as_ref().parent() {
+            if !dir.as_os_str().is_empty() {
+                std::fs::create_dir_all(dir)?;
+            }
+        }
+        let json = serde_json::to_string_pretty(self)?;
+        std::fs::write(path, json)?;
+        Ok(())
+    }
+
+    pub fn validate(&self) -> Result<(), ManifestError> {
+        if self.version != MANIFEST_VERSION {
+            return Err(ManifestError::UnsupportedVersion(self.version));
+        }
+        for (network, markets) in &self.networks {
+            for (market, deployment) in markets {
+                if *market != deployment.market() {
+                    return Err(ManifestError::InvalidField {
+                        network: network.clone(),
+                        market: market.clone(),
+                        field: "market",
+                        reason: format!("key doesn't match assets {}", deployment.market()),
+                    });
+                }
+                deployment.validate(network)?;
+            }
+        }
+        Ok(())
+    }
+
+    pub fn get(
+        &self,
+        network: &str,
+        base_symbol: &str,
+        quote_symbol: &str,
+    ) -> Option<&MarketDeployment> {
+        self.networks
+            .get(network)?
+            .get(&market_key(base_symbol, quote_symbol))
+    }
+
+    pub fn markets(&self, network: &str) -> impl Iterator<Item = &MarketDeployment> {
+        self.networks
+            .get(network)
+            .into_iter()
+            .flat_map(|m| m.values())
+    }
+
+    /// Adds a deployment, replacing the previous one of the same market on that network.
+    pub fn insert(
+        &mut self,
+        network: &str,
+        deployment: MarketDeployment,
+    ) -> Result<(), ManifestError> {
+        deployment.validate(network)?;
+        self.networks
+            .entry(network.to_string())
+            .or_default()
+            .insert(deployment.market(), deployment);
+        Ok(())
+    }
+
+    /// Pulls in every deployment of `other`. Identical entries are fine, a market that was
+    /// deployed differently in both manifests is a conflict and nothing gets merged.
+    pub fn merge(&mut self, other: DeploymentManifest) -> Result<(), ManifestError> {
+        other.validate()?;
+        for (network, markets) in &other.networks {
+            for (market, deployment) in markets {
+                if let Some(existing) = self.networks.get(network).and_then(|m| m.get(market)) {
+                    if existing != deployment {
+                        return Err(ManifestError::Conflict {
+                            network: network.clone(),
+                            market: market.clone(),
+                        });
+                    }
+                }
+            }
+        }
+        for (network, markets) in other.networks {
+            self.networks.entry(network).or_default().extend(markets);
+        }
+        Ok(())
+    }
+}
+
+pub fn bytecode_hash(bytecode: &[u8]) -> String {
+    format!("0x{}", hex_encode(&Sha256::digest(bytecode)))
+}
+
+pub fn file_hash(path: impl AsRef<Path>) -> String {
+    bytecode_hash(&std::fs::read(path).unwrap())
+}
+
+fn strip_0x(value: &str) -> &str {
+    value.strip_prefix("0x").unwrap_or(value)
+}
+
+fn check_b256(value: &str) -> Result<(), String> {
+    let hex = strip_0x(value);
+    if hex.len() != 64 {
+        return Err(format!("expected 32 bytes hex, got {} chars", hex.len()));
+    }
+    if !hex.chars().all(|c| c.is_ascii_hexdigit()) {
+        return Err("not a hex string".to_string());
+    }
+    Ok(())
+}
+
+fn hex_encode(bytes: &[u8]) -> String {
+    bytes.iter().map(|b| format!("{b:02x}")).collect()
+}
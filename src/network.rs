@@ -0,0 +1,271 @@
+use std::collections::HashMap;
+use std::env;
+use std::fmt;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+
+use fuels::prelude::Provider;
+use fuels::types::{AssetId, ContractId};
+use serde::{Deserialize, Serialize};
+
+use crate::constants::{
+    BTC_ASSET_ID_STR, LOCAL_RPC, TESTNET_RPC, TOKEN_CONTRACT_ID, USDC_ASSET_ID_STR,
+};
+use crate::manifest::{DeploymentManifest, ManifestError};
+
+// Env variables used to pick and tweak a profile at runtime
+pub const NETWORK_ENV: &str = "SPARK_NETWORK";
+pub const CONFIG_ENV: &str = "SPARK_CONFIG";
+pub const RPC_ENV: &str = "SPARK_RPC";
+pub const TOKEN_CONTRACT_ENV: &str = "SPARK_TOKEN_CONTRACT_ID";
+pub const START_BLOCK_ENV: &str = "SPARK_START_BLOCK";
+
+pub const DEFAULT_NETWORK: &str = "testnet";
+pub const DEFAULT_CONFIG_FILE: &str = "spark.json";
+
+#[derive(Debug)]
+pub enum NetworkError {
+    Io(std::io::Error),
+    Json(serde_json::Error),
+    Manifest(ManifestError),
+    Provider(fuels::types::errors::Error),
+    UnknownProfile(String),
+    MissingField {
+        profile: String,
+        field: &'static str,
+    },
+    InvalidField {
+        profile: String,
+        field: String,
+        value: String,
+    },
+}
+
+impl fmt::Display for NetworkError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            NetworkError::Io(e) => write!(f, "network config io error: {e}"),
+            NetworkError::Json(e) => write!(f, "network config json error: {e}"),
+            NetworkError::Manifest(e) => write!(f, "{e}"),
+            NetworkError::Provider(e) => write!(f, "can't connect to the node: {e}"),
+            NetworkError::UnknownProfile(name) => write!(f, "unknown network profile `{name}`"),
+            NetworkError::MissingField { profile, field } => {
+                write!(f, "`{field}` is not configured for `{profile}`")
+            }
+            NetworkError::InvalidField {
+                profile,
+                field,
+                value,
+            } => write!(f, "{profile}: invalid `{field}` `{value}`"),
+        }
+    }
+}
+
+impl std::error::Error for NetworkError {}
+
+impl From<std::io::Error> for NetworkError {
+    fn from(e: std::io::Error) -> Self {
+        NetworkError::Io(e)
+    }
+}
+
+impl From<serde_json::Error> for NetworkError {
+    fn from(e: serde_json::Error) -> Self {
+        NetworkError::Json(e)
+    }
+}
+
+impl From<ManifestError> for NetworkError {
+    fn from(e: ManifestError) -> Self {
+        NetworkError::Manifest(e)
+    }
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone, Default)]
+pub struct NetworkProfile {
+    #[serde(default)]
+    pub name: String,
+    pub rpc: String,
+    #[serde(default)]
+    pub token_contract_id: Option<String>,
+    // symbol => asset id
+    #[serde(default)]
+    pub assets: HashMap<String, String>,
+    // "BASE/QUOTE" => proxy contract id
+    #[serde(default)]
+    pub proxies: HashMap<String, String>,
+    #[serde(default)]
+    pub start_block: u32,
+}
+
+impl NetworkProfile {
+    pub fn local() -> Self {
+        Self {
+            name: "local".to_string(),
+            rpc: LOCAL_RPC.to_string(),
+            ..Default::default()
+        }
+    }
+
+    // No proxies: the old BTC/USDC one predates `market_info`, a redeployed one comes from
+    // spark.json or the deployment manifest
+    pub fn testnet() -> Self {
+        Self {
+            name: "testnet".to_string(),
+            rpc: TESTNET_RPC.to_string(),
+            token_contract_id: Some(TOKEN_CONTRACT_ID.to_string()),
+            assets: HashMap::from([
+                ("USDC".to_string(), USDC_ASSET_ID_STR.to_string()),
+                ("BTC".to_string(), BTC_ASSET_ID_STR.to_string()),
+            ]),
+            ..Default::default()
+        }
+    }
+
+    /// Profile named by `SPARK_NETWORK` (testnet by default).
+    pub fn from_env() -> Result<Self, NetworkError> {
+        let name = env::var(NETWORK_ENV).unwrap_or(DEFAULT_NETWORK.to_string());
+        Self::load(&name)
+    }
+
+    /// Looks the profile up in the config file first, then in the built-in profiles,
+    /// adds the proxies of the deployment manifest and applies the `SPARK_*` env overrides on top.
+    pub fn load(name: &str) -> Result<Self, NetworkError> {
+        let mut profile = NetworkConfig::from_env()?
+            .profiles
+            .remove(name)
+            .or_else(|| builtin_profile(name))
+            .ok_or_else(|| NetworkError::UnknownProfile(name.to_string()))?;
+        profile.name = name.to_string();
+        profile.apply_manifest(&DeploymentManifest::from_env()?);
+        profile.apply_env_overrides()?;
+        Ok(profile)
+    }
+
+    pub fn apply_manifest(&mut self, manifest: &DeploymentManifest) {
+        for deployment in manifest.markets(&self.name) {
+            self.proxies
+                .insert(deployment.market(), deployment.proxy.clone());
+            for asset in [&deployment.base_asset, &deployment.quote_asset] {
+                self.assets
+                    .insert(asset.symbol.clone(), asset.asset_id.clone());
+            }
+        }
+        // the indexer has to start from the oldest market
+        let start_block = manifest.markets(&self.name).map(|d| d.start_block).min();
+        if let Some(start_block) = start_block {
+            self.start_block = start_block;
+        }
+    }
+
+    pub fn apply_env_overrides(&mut self) -> Result<(), NetworkError> {
+        if let Ok(rpc) = env::var(RPC_ENV) {
+            self.rpc = rpc;
+        }
+        if let Ok(token_contract_id) = env::var(TOKEN_CONTRACT_ENV) {
+            self.token_contract_id = Some(token_contract_id);
+        }
+        if let Ok(start_block) = env::var(START_BLOCK_ENV) {
+            self.start_block = start_block
+                .parse()
+                .map_err(|_| self.invalid(START_BLOCK_ENV, &start_block))?;
+        }
+        Ok(())
+    }
+
+    pub async fn connect(&self) -> Result<Provider, NetworkError> {
+        Provider::connect(&self.rpc)
+            .await
+            .map_err(NetworkError::Provider)
+    }
+
+    pub fn token_contract_id(&self) -> Result<ContractId, NetworkError> {
+        let id = self
+            .token_contract_id
+            .as_ref()
+            .ok_or_else(|| NetworkError::MissingField {
+                profile: self.name.clone(),
+                field: "token_contract_id",
+            })?;
+        ContractId::from_str(id).map_err(|_| self.invalid("token_contract_id", id))
+    }
+
+    pub fn asset_id(&self, symbol: &str) -> Result<Option<AssetId>, NetworkError> {
+        self.assets
+            .get(symbol)
+            .map(|id| AssetId::from_str(id).map_err(|_| self.invalid(symbol, id)))
+            .transpose()
+    }
+
+    pub fn proxy(
+        &self,
+        base_symbol: &str,
+        quote_symbol: &str,
+    ) -> Result<Option<ContractId>, NetworkError> {
+        let market = market_key(base_symbol, quote_symbol);
+        self.proxies
+            .get(&market)
+            .map(|id| ContractId::from_str(id).map_err(|_| self.invalid(&market, id)))
+            .transpose()
+    }
+
+    pub fn set_proxy(&mut self, base_symbol: &str, quote_symbol: &str, proxy: ContractId) {
+        self.proxies
+            .insert(market_key(base_symbol, quote_symbol), format!("0x{proxy}"));
+    }
+
+    fn invalid(&self, field: &str, value: &str) -> NetworkError {
+        NetworkError::InvalidField {
+            profile: self.name.clone(),
+            field: field.to_string(),
+            value: value.to_string(),
+        }
+    }
+}
+
+#[derive(Deserialize, Serialize, Debug, Default)]
+pub struct NetworkConfig {
+    #[serde(flatten)]
+    pub profiles: HashMap<String, NetworkProfile>,
+}
+
+impl NetworkConfig {
+    /// Reads `SPARK_CONFIG` or `./spark.json`; a missing file is an empty config.
+    pub fn from_env() -> Result<Self, NetworkError> {
+        let path = Self::path_from_env();
+        if path.exists() {
+            Self::load(path)
+        } else {
+            Ok(Self::default())
+        }
+    }
+
+    pub fn path_from_env() -> PathBuf {
+        env::var(CONFIG_ENV)
+            .map(PathBuf::from)
+            .unwrap_or(PathBuf::from(DEFAULT_CONFIG_FILE))
+    }
+
+    pub fn load(path: impl AsRef<Path>) -> Result<Self, NetworkError> {
+        let json = std::fs::read_to_string(path)?;
+        Ok(serde_json::from_str(&json)?)
+    }
+
+    pub fn save(&self, path: impl AsRef<Path>) -> Result<(), NetworkError> {
+        let json = serde_json::to_string_pretty(self)?;
+        std::fs::write(path, json)?;
+        Ok(())
+    }
+}
+
+pub fn builtin_profile(name: &str) -> Option<NetworkProfile> {
+    match name {
+        "local" => Some(NetworkProfile::local()),
+        "testnet" => Some(NetworkProfile::testnet()),
+        _ => None,
+    }
+}
+
+pub fn market_key(base_symbol: &str, quote_symbol: &str) -> String {
+    format!("{base_symbol}/{quote_symbol}")
+}
@@ -0,0 +1,52 @@
+use crate::order::quote_amount_for;
+use fuels::types::Address;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OcoLeg {
+    // anyone, at the take-profit price or better
+    TakeProfit,
+    // only the stop keeper, at the stop price or better
+    Stop,
+}
+
+// A sell order with two exits drawing on the same coins, see predicate-oco
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct OcoOrder {
+    pub take_profit_price: u64,
+    pub stop_price: u64,
+    pub stop_keeper: Address,
+}
+
+impl OcoOrder {
+    pub fn new(take_profit_price: u64, stop_price: u64, stop_keeper: Address) -> Self {
+        Self {
+            take_profit_price,
+            stop_price,
+            stop_keeper,
+        }
+    }
+
+    pub fn is_valid(&self) -> bool {
+        self.take_profit_price > self.stop_price
+            && self.stop_price > 0
+            && self.stop_keeper != Address::zeroed()
+    }
+
+    pub fn price(&self, leg: OcoLeg) -> u64 {
+        match leg {
+            OcoLeg::TakeProfit => self.take_profit_price,
+            OcoLeg::Stop => self.stop_price,
+        }
+    }
+
+    /// Quote a fill of the whole `base_amount` through `leg` has to pay.
+    pub fn quote_amount(
+        &self,
+        leg: OcoLeg,
+        base_amount: u64,
+        base_decimals: u32,
+        quote_decimals: u32,
+    ) -> u64 {
+        quote_amount_for(base_amount, self.price(leg), base_decimals, quote_decimals)
+    }
+}
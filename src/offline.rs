@@ -0,0 +1,343 @@
+use std::fmt;
+use std::path::Path;
+
+use fuel_types::canonical::{Deserialize as _, Serialize as _};
+use fuels::accounts::predicate::Predicate;
+use fuels::accounts::wallet::WalletUnlocked;
+use fuels::prelude::{Bech32Address, Bech32ContractId, Provider};
+use fuels::tx::field::{Inputs, Outputs};
+use fuels::tx::{Input, Output, Script, UniqueIdentifier, UtxoId};
+use fuels::types::transaction::ScriptTransaction;
+use fuels::types::{Address, AssetId, Bytes32, ChainId, ContractId};
+use serde::{Deserialize, Serialize};
+
+use crate::signer::{sign_transaction, Signer};
+use crate::spark_utils::{CancelOrderEvent, CreateOrderEvent, ProxyContract};
+
+pub const OFFLINE_TX_VERSION: u32 = 1;
+// Binary artifacts: magic, u32 BE header length, JSON header without `tx`, raw tx bytes
+const BINARY_MAGIC: &[u8; 4] = b"SPTX";
+
+#[derive(Debug)]
+pub enum OfflineError {
+    Io(std::io::Error),
+    Json(serde_json::Error),
+    Fuels(fuels::types::errors::Error),
+    UnsupportedVersion(u32),
+    InvalidEncoding(String),
+    WrongSigner { expected: String, got: String },
+    NotSigned,
+}
+
+impl fmt::Display for OfflineError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            OfflineError::Io(e) => write!(f, "offline tx io error: {e}"),
+            OfflineError::Json(e) => write!(f, "offline tx json error: {e}"),
+            OfflineError::Fuels(e) => write!(f, "{e}"),
+            OfflineError::UnsupportedVersion(v) => {
+                write!(
+                    f,
+                    "unsupported offline tx version {v}, expected {OFFLINE_TX_VERSION}"
+                )
+            }
+            OfflineError::InvalidEncoding(reason) => write!(f, "invalid offline tx: {reason}"),
+            OfflineError::WrongSigner { expected, got } => {
+                write!(f, "tx has to be signed by {expected}, not {got}")
+            }
+            OfflineError::NotSigned => write!(f, "tx isn't signed yet"),
+        }
+    }
+}
+
+impl std::error::Error for OfflineError {}
+
+impl From<std::io::Error> for OfflineError {
+    fn from(e: std::io::Error) -> Self {
+        OfflineError::Io(e)
+    }
+}
+
+impl From<serde_json::Error> for OfflineError {
+    fn from(e: serde_json::Error) -> Self {
+        OfflineError::Json(e)
+    }
+}
+
+impl From<fuels::types::errors::Error> for OfflineError {
+    fn from(e: fuels::types::errors::Error) -> Self {
+        OfflineError::Fuels(e)
+    }
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum OfflineAction {
+    CreateOrder,
+    CancelOrder,
+    FulfillOrder,
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OfflineFormat {
+    Json,
+    Binary,
+}
+
+// A coin the tx spends, spelled out so it can be reviewed on the offline machine
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq, Eq)]
+pub struct OfflineUtxo {
+    pub utxo_id: String,
+    pub owner: String,
+    pub asset_id: String,
+    pub amount: u64,
+    pub predicate: bool,
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq, Eq)]
+pub struct OfflineTx {
+    pub version: u32,
+    pub action: OfflineAction,
+    pub chain_id: u64,
+    pub proxy: String,
+    // the address whose signature the tx is waiting for
+    pub signer: String,
+    pub signed: bool,
+    pub predicate_root: String,
+    // with the order's configurables already applied
+    pub predicate_bytecode: String,
+    pub utxos: Vec<OfflineUtxo>,
+    // canonically encoded fuel tx, placeholder witnesses until signed
+    #[serde(default)]
+    pub tx: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CoinTransfer {
+    pub to: Address,
+    pub asset_id: AssetId,
+    pub amount: u64,
+}
+
+#[derive(Debug)]
+pub enum OfflineOutcome {
+    OrderCreated {
+        tx_id: Bytes32,
+        events: Vec<CreateOrderEvent>,
+    },
+    // Fills are plain predicate spends, the coin outputs are the outcome
+    OrderFilled {
+        tx_id: Bytes32,
+        transfers: Vec<CoinTransfer>,
+    },
+    // Cancels call the proxy's `cancel_order`, which logs them
+    OrderCancelled {
+        tx_id: Bytes32,
+        transfers: Vec<CoinTransfer>,
+        events: Vec<CancelOrderEvent>,
+    },
+}
+
+impl OfflineTx {
+    pub fn new(
+        action: OfflineAction,
+        tx: ScriptTransaction,
+        chain_id: ChainId,
+        proxy: &Bech32ContractId,
+        signer: &Bech32Address,
+        predicate: &Predicate,
+    ) -> Self {
+        let script: Script = tx.into();
+        let utxos = script.inputs().iter().filter_map(offline_utxo).collect();
+        Self {
+            version: OFFLINE_TX_VERSION,
+            action,
+            chain_id: u64::from(chain_id),
+            proxy: format!("0x{}", ContractId::from(proxy)),
+            signer: format!("0x{}", Address::from(signer)),
+            signed: false,
+            predicate_root: format!("0x{}", Address::from(predicate.address())),
+            predicate_bytecode: hex::encode(predicate.code()),
+            utxos,
+            tx: hex::encode(fuels::tx::Transaction::from(script).to_bytes()),
+        }
+    }
+
+    pub fn transaction(&self) -> Result<ScriptTransaction, OfflineError> {
+        Ok(self.script()?.into())
+    }
+
+    pub fn id(&self) -> Result<Bytes32, OfflineError> {
+        Ok(self.script()?.id(&self.chain_id()))
+    }
+
+    pub fn chain_id(&self) -> ChainId {
+        ChainId::new(self.chain_id)
+    }
+
+    /// Signs with `signer`, which has to be the address the tx was built for.
+    pub async fn sign(&mut self, signer: &impl Signer) -> Result<(), OfflineError> {
+        let got = format!("0x{}", Address::from(signer.address()));
+        if got != self.signer {
+            return Err(OfflineError::WrongSigner {
+                expected: self.signer.clone(),
+                got,
+            });
+        }
+        let tx = sign_transaction(signer, self.transaction()?, self.chain_id()).await?;
+        let script: Script = tx.into();
+        self.tx = hex::encode(fuels::tx::Transaction::from(script).to_bytes());
+        self.signed = true;
+        Ok(())
+    }
+
+    /// Broadcasts the signed tx, waits for it to be committed and decodes what happened.
+    pub async fn submit(&self, provider: &Provider) -> Result<OfflineOutcome, OfflineError> {
+        if !self.signed {
+            return Err(OfflineError::NotSigned);
+        }
+        let script = self.script()?;
+        let tx_id = script.id(&provider.chain_id());
+        let transfers = script.outputs().iter().filter_map(coin_transfer).collect();
+
+        let receipts = provider
+            .send_transaction_and_await_commit(ScriptTransaction::from(script))
+            .await?
+            .take_receipts_checked(None)?;
+
+        let proxy_id: ContractId = parse_hex(&self.proxy)?;
+        let log_decoder =
+            ProxyContract::new(proxy_id, WalletUnlocked::new_random(Some(provider.clone())))
+                .log_decoder();
+        Ok(match self.action {
+            OfflineAction::CreateOrder => {
+                let events = log_decoder.decode_logs_with_type::<CreateOrderEvent>(&receipts)?;
+                OfflineOutcome::OrderCreated { tx_id, events }
+            }
+            OfflineAction::FulfillOrder => OfflineOutcome::OrderFilled { tx_id, transfers },
+            OfflineAction::CancelOrder => {
+                let events = log_decoder.decode_logs_with_type::<CancelOrderEvent>(&receipts)?;
+                OfflineOutcome::OrderCancelled {
+                    tx_id,
+                    transfers,
+                    events,
+                }
+            }
+        })
+    }
+
+    pub fn save(&self, path: impl AsRef<Path>, format: OfflineFormat) -> Result<(), OfflineError> {
+        std::fs::write(path, self.encode(format)?)?;
+        Ok(())
+    }
+
+    /// Reads a JSON or binary artifact, whichever `path` holds.
+    pub fn load(path: impl AsRef<Path>) -> Result<Self, OfflineError> {
+        Self::decode(&std::fs::read(path)?)
+    }
+
+    pub fn encode(&self, format: OfflineFormat) -> Result<Vec<u8>, OfflineError> {
+        match format {
+            OfflineFormat::Json => Ok(serde_json::to_vec_pretty(self)?),
+            OfflineFormat::Binary => {
+                let tx = hex::decode(&self.tx)
+                    .map_err(|e| OfflineError::InvalidEncoding(e.to_string()))?;
+                let header = serde_json::to_vec(&Self {
+                    tx: String::new(),
+                    ..self.clone()
+                })?;
+                let mut bytes = BINARY_MAGIC.to_vec();
+                bytes.extend_from_slice(&(header.len() as u32).to_be_bytes());
+                bytes.extend_from_slice(&header);
+                bytes.extend_from_slice(&tx);
+                Ok(bytes)
+            }
+        }
+    }
+
+    pub fn decode(bytes: &[u8]) -> Result<Self, OfflineError> {
+        let offline_tx: Self = match bytes.strip_prefix(BINARY_MAGIC) {
+            Some(rest) => {
+                let truncated = || OfflineError::InvalidEncoding("truncated binary".to_string());
+                if rest.len() < 4 {
+                    return Err(truncated());
+                }
+                let (len, rest) = rest.split_at(4);
+                let len = u32::from_be_bytes(len.try_into().unwrap()) as usize;
+                if rest.len() < len {
+                    return Err(truncated());
+                }
+                let (header, tx) = rest.split_at(len);
+                Self {
+                    tx: hex::encode(tx),
+                    ..serde_json::from_slice(header)?
+                }
+            }
+            None => serde_json::from_slice(bytes)?,
+        };
+        if offline_tx.version != OFFLINE_TX_VERSION {
+            return Err(OfflineError::UnsupportedVersion(offline_tx.version));
+        }
+        offline_tx.script()?;
+        Ok(offline_tx)
+    }
+
+    fn script(&self) -> Result<Script, OfflineError> {
+        let bytes =
+            hex::decode(&self.tx).map_err(|e| OfflineError::InvalidEncoding(e.to_string()))?;
+        match fuels::tx::Transaction::from_bytes(&bytes) {
+            Ok(fuels::tx::Transaction::Script(script)) => Ok(script),
+            Ok(_) => Err(OfflineError::InvalidEncoding("not a script tx".to_string())),
+            Err(e) => Err(OfflineError::InvalidEncoding(format!("{e:?}"))),
+        }
+    }
+}
+
+fn offline_utxo(input: &Input) -> Option<OfflineUtxo> {
+    let utxo =
+        |utxo_id: UtxoId, owner: &Address, asset_id: &AssetId, amount, predicate| OfflineUtxo {
+            utxo_id: format!("0x{}{:02x}", utxo_id.tx_id(), utxo_id.output_index()),
+            owner: format!("0x{owner}"),
+            asset_id: format!("0x{asset_id}"),
+            amount,
+            predicate,
+        };
+    match input {
+        Input::CoinSigned(coin) => Some(utxo(
+            coin.utxo_id,
+            &coin.owner,
+            &coin.asset_id,
+            coin.amount,
+            false,
+        )),
+        Input::CoinPredicate(coin) => Some(utxo(
+            coin.utxo_id,
+            &coin.owner,
+            &coin.asset_id,
+            coin.amount,
+            true,
+        )),
+        _ => None,
+    }
+}
+
+fn coin_transfer(output: &Output) -> Option<CoinTransfer> {
+    match output {
+        Output::Coin {
+            to,
+            amount,
+            asset_id,
+        } => Some(CoinTransfer {
+            to: *to,
+            asset_id: *asset_id,
+            amount: *amount,
+        }),
+        _ => None,
+    }
+}
+
+fn parse_hex<T: std::str::FromStr>(value: &str) -> Result<T, OfflineError> {
+    value
+        .parse()
+        .map_err(|_| OfflineError::InvalidEncoding(format!("bad hex value {value}")))
+}
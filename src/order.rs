@@ -0,0 +1,390 @@
+use crate::spark_utils::PRICE_DECIMALS;
+use fuels::prelude::Provider;
+use fuels::types::{Address, AssetId, Bits256, Identity};
+use primitive_types::U256;
+use rand::Rng;
+use sha2::{Digest, Sha256};
+use std::fmt;
+use std::num::NonZeroU64;
+
+// TAI64 label of the unix epoch, what fuel block timestamps are counted from
+pub const TAI64_UNIX_EPOCH: u64 = (1 << 62) + 10;
+
+// When an order stops accepting fills. Stored in the predicate's EXPIRY configurable as one
+// u64: 0 is never, values from 2^62 up are TAI64 timestamps, anything below a block height.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Expiry {
+    #[default]
+    Never,
+    // last block a fill can land in
+    BlockHeight(u32),
+    // unix seconds, last block time a fill can land at
+    Timestamp(u64),
+}
+
+impl Expiry {
+    pub fn to_configurable(&self) -> u64 {
+        match self {
+            Expiry::Never => 0,
+            Expiry::BlockHeight(height) => *height as u64,
+            Expiry::Timestamp(unix) => TAI64_UNIX_EPOCH + unix,
+        }
+    }
+
+    pub fn from_configurable(value: u64) -> Self {
+        if value == 0 {
+            Expiry::Never
+        } else if value >= 1 << 62 {
+            Expiry::Timestamp(value.saturating_sub(TAI64_UNIX_EPOCH))
+        } else {
+            Expiry::BlockHeight(value as u32)
+        }
+    }
+
+    pub fn is_expired(&self, block_height: u32, unix_time: u64) -> bool {
+        match self {
+            Expiry::Never => false,
+            Expiry::BlockHeight(height) => block_height > *height,
+            Expiry::Timestamp(unix) => unix_time > *unix,
+        }
+    }
+
+    /// Whether a fill would still land before the expiry, judging by the latest block.
+    pub async fn is_expired_on(&self, provider: &Provider) -> Result<bool, fuels::prelude::Error> {
+        if *self == Expiry::Never {
+            return Ok(false);
+        }
+        let height = provider.latest_block_height().await?;
+        let time = provider
+            .latest_block_time()
+            .await?
+            .map(|time| time.timestamp() as u64)
+            .unwrap_or_default();
+        Ok(self.is_expired(height, time))
+    }
+}
+
+pub const BPS_DENOMINATOR: u64 = 10_000;
+
+// Paid by the taker on top of every fill, in the asset the maker receives
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct OrderFee {
+    pub recipient: Address,
+    pub bps: u64,
+}
+
+impl OrderFee {
+    pub fn new(recipient: Address, bps: u64) -> Self {
+        Self { recipient, bps }
+    }
+
+    /// Fee the predicate asks for when the maker receives `amount`, rounded down like on-chain.
+    pub fn fee_for(&self, amount: u64) -> u64 {
+        (amount as u128 * self.bps as u128 / BPS_DENOMINATOR as u128) as u64
+    }
+}
+
+// The optional order terms baked into a predicate
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct OrderOptions {
+    // in the asset the predicate holds: quote for buy orders, base for sell orders
+    pub min_fulfill_amount: u64,
+    // fills have to run the expiry script, so they can't be contract calls: no
+    // `fulfill_order_via_proxy` and no `FillEvent` for expiring orders
+    pub expiry: Expiry,
+    // zero keeps the root of orders made before nonces, see `with_random_nonce`
+    pub nonce: Bits256,
+    pub fee: Option<OrderFee>,
+    // where fills pay the proceeds, the maker when unset
+    pub recipient: Option<Address>,
+    // the only taker that can fill, anyone when unset
+    pub allowed_taker: Option<Address>,
+    // fills have to take the whole balance
+    pub all_or_none: bool,
+}
+
+impl Default for OrderOptions {
+    fn default() -> Self {
+        Self {
+            min_fulfill_amount: 1,
+            expiry: Expiry::Never,
+            nonce: Bits256([0; 32]),
+            fee: None,
+            recipient: None,
+            allowed_taker: None,
+            all_or_none: false,
+        }
+    }
+}
+
+impl OrderOptions {
+    pub fn with_min_fulfill_amount(mut self, min_fulfill_amount: u64) -> Self {
+        self.min_fulfill_amount = min_fulfill_amount;
+        self
+    }
+
+    /// Fills of an expiring order have to run the expiry script as the tx script. A contract call
+    /// can't be one, so `fulfill_order_via_proxy` rejects these orders and their fills are never
+    /// logged as `FillEvent`s.
+    pub fn with_expiry(mut self, expiry: Expiry) -> Self {
+        self.expiry = expiry;
+        self
+    }
+
+    pub fn with_nonce(mut self, nonce: Bits256) -> Self {
+        self.nonce = nonce;
+        self
+    }
+
+    pub fn with_fee(mut self, fee: OrderFee) -> Self {
+        self.fee = Some(fee);
+        self
+    }
+
+    /// Proceeds go to `recipient` while the maker keeps the right to cancel.
+    pub fn with_recipient(mut self, recipient: Address) -> Self {
+        self.recipient = Some(recipient);
+        self
+    }
+
+    /// Makes the order private, only fills signed by `taker` go through.
+    pub fn with_allowed_taker(mut self, taker: Address) -> Self {
+        self.allowed_taker = Some(taker);
+        self
+    }
+
+    /// Fill-or-kill: the predicate rejects any fill that leaves some of the balance behind.
+    pub fn with_all_or_none(mut self) -> Self {
+        self.all_or_none = true;
+        self
+    }
+
+    /// A fresh nonce, so the order gets a root of its own even if the maker already has an
+    /// order with the same terms.
+    pub fn with_random_nonce(self) -> Self {
+        self.with_nonce(random_nonce())
+    }
+}
+
+pub fn random_nonce() -> Bits256 {
+    Bits256(rand::thread_rng().gen())
+}
+
+/// The id the proxy logs and returns for an order, computable before it's created:
+/// sha256(predicate_root ++ maker ++ price ++ base_asset ++ quote_asset ++ nonce ++ amount)
+/// with big-endian u64s. `amount` is what gets paid into the predicate.
+pub fn order_id(
+    predicate_root: &Address,
+    maker: &Identity,
+    price: u64,
+    base_asset: &AssetId,
+    quote_asset: &AssetId,
+    nonce: &Bits256,
+    amount: u64,
+) -> Bits256 {
+    let maker: [u8; 32] = match maker {
+        Identity::Address(address) => **address,
+        Identity::ContractId(contract_id) => **contract_id,
+    };
+    let mut hasher = Sha256::new();
+    hasher.update(**predicate_root);
+    hasher.update(maker);
+    hasher.update(price.to_be_bytes());
+    hasher.update(**base_asset);
+    hasher.update(**quote_asset);
+    hasher.update(nonce.0);
+    hasher.update(amount.to_be_bytes());
+    Bits256(hasher.finalize().into())
+}
+
+// Quote units paid per base unit. A plain u64 converts to `Scaled`: the price times
+// 10^(PRICE_DECIMALS + base decimals - quote decimals). `Ratio` is exactly `num / den`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Price {
+    Scaled(u64),
+    Ratio { num: NonZeroU64, den: NonZeroU64 },
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PriceError {
+    ZeroNumerator,
+    ZeroDenominator,
+}
+
+impl fmt::Display for PriceError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            PriceError::ZeroNumerator => write!(f, "a price can't be zero"),
+            PriceError::ZeroDenominator => write!(f, "a price can't divide by zero"),
+        }
+    }
+}
+
+impl std::error::Error for PriceError {}
+
+impl Price {
+    /// Exactly `num / den` quote units per base unit, for prices the scaled u64 can't hold.
+    pub fn new(num: u64, den: u64) -> Result<Self, PriceError> {
+        let num = NonZeroU64::new(num).ok_or(PriceError::ZeroNumerator)?;
+        let den = NonZeroU64::new(den).ok_or(PriceError::ZeroDenominator)?;
+        Ok(Price::Ratio { num, den })
+    }
+
+    pub fn scaled(price: u64) -> Self {
+        Price::Scaled(price)
+    }
+
+    /// The PRICE and PRICE_DEN configurables, PRICE_DEN is 0 for a scaled price.
+    pub fn to_configurables(&self) -> (u64, u64) {
+        match *self {
+            Price::Scaled(price) => (price, 0),
+            Price::Ratio { num, den } => (num.get(), den.get()),
+        }
+    }
+
+    /// The fraction the predicates compare against, with the scale filled in.
+    pub fn fraction(&self, base_decimals: u32, quote_decimals: u32) -> (U256, U256) {
+        match *self {
+            Price::Scaled(price) => {
+                let (up, down) = decimals_scale(PRICE_DECIMALS, base_decimals, quote_decimals);
+                (U256::from(price) * down, up)
+            }
+            Price::Ratio { num, den } => (U256::from(num.get()), U256::from(den.get())),
+        }
+    }
+
+    /// Quote `base_amount` costs, rounded up so the maker of a sell order is never short.
+    /// Panics if that's more than a u64 holds, see `checked_quote_amount`.
+    pub fn quote_amount(&self, base_amount: u64, base_decimals: u32, quote_decimals: u32) -> u64 {
+        self.checked_quote_amount(base_amount, base_decimals, quote_decimals)
+            .expect("quote amount doesn't fit in a u64")
+    }
+
+    /// Base `quote_amount` costs, rounded up so the maker of a buy order is never short.
+    /// Panics if that's more than a u64 holds, see `checked_base_amount`.
+    pub fn base_amount(&self, quote_amount: u64, base_decimals: u32, quote_decimals: u32) -> u64 {
+        self.checked_base_amount(quote_amount, base_decimals, quote_decimals)
+            .expect("base amount doesn't fit in a u64")
+    }
+
+    /// `quote_amount`, or None when no u64 amount of quote is enough.
+    pub fn checked_quote_amount(
+        &self,
+        base_amount: u64,
+        base_decimals: u32,
+        quote_decimals: u32,
+    ) -> Option<u64> {
+        let (num, den) = self.fraction(base_decimals, quote_decimals);
+        to_u64(div_ceil(U256::from(base_amount) * num, den))
+    }
+
+    /// `base_amount`, or None when no u64 amount of base is enough, as with a zero scaled
+    /// price.
+    pub fn checked_base_amount(
+        &self,
+        quote_amount: u64,
+        base_decimals: u32,
+        quote_decimals: u32,
+    ) -> Option<u64> {
+        let (num, den) = self.fraction(base_decimals, quote_decimals);
+        if num.is_zero() {
+            return None;
+        }
+        to_u64(div_ceil(U256::from(quote_amount) * den, num))
+    }
+}
+
+impl From<u64> for Price {
+    fn from(price: u64) -> Self {
+        Price::scaled(price)
+    }
+}
+
+// How far the decimals can shift a price either way. The predicates multiply a u64 amount by
+// a u64 price and by the scale in 256 bits, 10^38 is the most that still fits.
+pub const MAX_SCALE_EXPONENT: u32 = 38;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DecimalsError {
+    pub price_decimals: u32,
+    pub base_decimals: u32,
+    pub quote_decimals: u32,
+}
+
+impl fmt::Display for DecimalsError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "price decimals {} with base decimals {} and quote decimals {} scale prices by 10^{}, \
+             past 10^±{MAX_SCALE_EXPONENT}",
+            self.price_decimals,
+            self.base_decimals,
+            self.quote_decimals,
+            scale_exponent(self.price_decimals, self.base_decimals, self.quote_decimals)
+        )
+    }
+}
+
+impl std::error::Error for DecimalsError {}
+
+/// price_decimals + base_decimals - quote_decimals, negative when the quote asset has more
+/// decimals than the other two together.
+pub fn scale_exponent(price_decimals: u32, base_decimals: u32, quote_decimals: u32) -> i64 {
+    price_decimals as i64 + base_decimals as i64 - quote_decimals as i64
+}
+
+/// Whether the predicates and the proxy can do their price math for a market with these
+/// decimals without overflowing.
+pub fn validate_decimals(
+    price_decimals: u32,
+    base_decimals: u32,
+    quote_decimals: u32,
+) -> Result<(), DecimalsError> {
+    let exponent = scale_exponent(price_decimals, base_decimals, quote_decimals);
+    if exponent.unsigned_abs() > MAX_SCALE_EXPONENT as u64 {
+        return Err(DecimalsError {
+            price_decimals,
+            base_decimals,
+            quote_decimals,
+        });
+    }
+    Ok(())
+}
+
+/// 10^scale_exponent as `up / down`, one of them 1, the way the predicates compute it.
+pub fn decimals_scale(
+    price_decimals: u32,
+    base_decimals: u32,
+    quote_decimals: u32,
+) -> (U256, U256) {
+    let up = price_decimals + base_decimals;
+    if up >= quote_decimals {
+        (U256::exp10((up - quote_decimals) as usize), U256::one())
+    } else {
+        (U256::one(), U256::exp10((quote_decimals - up) as usize))
+    }
+}
+
+pub(crate) fn div_ceil(a: U256, b: U256) -> U256 {
+    let (quotient, remainder) = a.div_mod(b);
+    if remainder.is_zero() {
+        quotient
+    } else {
+        quotient + 1
+    }
+}
+
+pub(crate) fn to_u64(value: U256) -> Option<u64> {
+    u64::try_from(value).ok()
+}
+
+/// Quote worth `base_amount` at `price`, rounded up so a predicate checking the price with
+/// `quote_to_base_amount` accepts it.
+pub fn quote_amount_for(
+    base_amount: u64,
+    price: u64,
+    base_decimals: u32,
+    quote_decimals: u32,
+) -> u64 {
+    Price::scaled(price).quote_amount(base_amount, base_decimals, quote_decimals)
+}
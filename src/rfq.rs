@@ -0,0 +1,77 @@
+use crate::order::Expiry;
+use crate::signer::Signer;
+use fuels::crypto::{Message, Signature};
+use fuels::tx::UtxoId;
+use fuels::types::errors::Result;
+use fuels::types::{AssetId, B512};
+
+// A firm quote a maker streams to takers for one coin of the predicate, good for one fill of
+// up to `max_size` until it expires. `max_size` is in the asset the predicate holds. The fill
+// spends the coin, so the next quotes are for its change; `Spark::revoke_rfq_quotes` moves the
+// coin to drop every quote out for it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RfqQuote {
+    pub coin: UtxoId,
+    pub price: u64,
+    pub max_size: u64,
+    pub expiry: Expiry,
+    pub nonce: u64,
+}
+
+impl RfqQuote {
+    pub fn new(coin: UtxoId, price: u64, max_size: u64, expiry: Expiry, nonce: u64) -> Self {
+        Self {
+            coin,
+            price,
+            max_size,
+            expiry,
+            nonce,
+        }
+    }
+
+    /// What the maker signs and the predicate recovers the signer from:
+    /// sha256(base_asset ++ quote_asset ++ side ++ coin tx id ++ coin output index ++ price ++
+    /// max_size ++ expiry ++ nonce), u64s big-endian, side 1 for bids and 0 for asks.
+    pub fn message(&self, base_asset: &AssetId, quote_asset: &AssetId, buy: bool) -> Message {
+        let mut bytes = Vec::with_capacity(144);
+        bytes.extend_from_slice(&**base_asset);
+        bytes.extend_from_slice(&**quote_asset);
+        bytes.extend_from_slice(&(buy as u64).to_be_bytes());
+        bytes.extend_from_slice(&**self.coin.tx_id());
+        bytes.extend_from_slice(&(self.coin.output_index() as u64).to_be_bytes());
+        bytes.extend_from_slice(&self.price.to_be_bytes());
+        bytes.extend_from_slice(&self.max_size.to_be_bytes());
+        bytes.extend_from_slice(&self.expiry.to_configurable().to_be_bytes());
+        bytes.extend_from_slice(&self.nonce.to_be_bytes());
+        Message::new(bytes)
+    }
+
+    pub async fn sign(
+        self,
+        signer: &impl Signer,
+        base_asset: &AssetId,
+        quote_asset: &AssetId,
+        buy: bool,
+    ) -> Result<SignedRfqQuote> {
+        let signature = signer
+            .sign(self.message(base_asset, quote_asset, buy))
+            .await?;
+        Ok(SignedRfqQuote {
+            quote: self,
+            signature,
+        })
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SignedRfqQuote {
+    pub quote: RfqQuote,
+    pub signature: Signature,
+}
+
+impl SignedRfqQuote {
+    // the signature the way the predicate takes it
+    pub(crate) fn signature_b512(&self) -> B512 {
+        B512::try_from(self.signature.as_ref()).unwrap()
+    }
+}
@@ -0,0 +1,213 @@
+use std::fmt::Debug;
+
+use async_trait::async_trait;
+use fuels::accounts::{wallet::WalletUnlocked, Account, ViewOnlyAccount};
+use fuels::crypto::{Message, PublicKey, SecretKey, Signature};
+use fuels::prelude::{Bech32Address, Provider};
+use fuels::tx::field::{Inputs, Witnesses};
+use fuels::tx::{Script, UniqueIdentifier, Witness};
+use fuels::types::errors::{error, Result};
+use fuels::types::transaction::ScriptTransaction;
+use fuels::types::transaction_builders::TransactionBuilder;
+use fuels::types::{Address, ChainId};
+use tokio::sync::{mpsc, oneshot};
+
+// Anything that can produce a secp256k1 signature for an address: a local key, an HSM,
+// a remote signing service...
+#[async_trait]
+pub trait Signer: Debug + Send + Sync {
+    fn address(&self) -> &Bech32Address;
+    async fn sign(&self, message: Message) -> Result<Signature>;
+}
+
+#[derive(Debug, Clone)]
+pub struct LocalSigner {
+    secret_key: SecretKey,
+    address: Bech32Address,
+}
+
+impl LocalSigner {
+    pub fn new(secret_key: SecretKey) -> Self {
+        let public_key = PublicKey::from(&secret_key);
+        let address = Bech32Address::from(Address::from(*public_key.hash()));
+        Self {
+            secret_key,
+            address,
+        }
+    }
+}
+
+#[async_trait]
+impl Signer for LocalSigner {
+    fn address(&self) -> &Bech32Address {
+        &self.address
+    }
+
+    async fn sign(&self, message: Message) -> Result<Signature> {
+        Ok(Signature::sign(&self.secret_key, &message))
+    }
+}
+
+// Only knows the address. Enough to build a tx for a key that lives elsewhere (see
+// `offline`), never signs.
+#[derive(Debug, Clone)]
+pub struct WatchOnlySigner {
+    address: Bech32Address,
+}
+
+impl WatchOnlySigner {
+    pub fn new(address: impl Into<Bech32Address>) -> Self {
+        Self {
+            address: address.into(),
+        }
+    }
+}
+
+#[async_trait]
+impl Signer for WatchOnlySigner {
+    fn address(&self) -> &Bech32Address {
+        &self.address
+    }
+
+    async fn sign(&self, _message: Message) -> Result<Signature> {
+        Err(error!(
+            InvalidData,
+            "{} is watch-only, sign the tx offline", self.address
+        ))
+    }
+}
+
+type SignRequest = (Message, oneshot::Sender<Signature>);
+
+// Local stand-in for a remote signing service: the key lives in its own task and the
+// client only ever sees messages going in and signatures coming out.
+#[derive(Debug, Clone)]
+pub struct RemoteSigner {
+    address: Bech32Address,
+    requests: mpsc::Sender<SignRequest>,
+}
+
+impl RemoteSigner {
+    pub fn spawn_local_service(secret_key: SecretKey) -> Self {
+        let signer = LocalSigner::new(secret_key);
+        let address = signer.address().clone();
+        let (requests, mut receiver) = mpsc::channel::<SignRequest>(16);
+        tokio::spawn(async move {
+            while let Some((message, reply)) = receiver.recv().await {
+                let signature = signer.sign(message).await.unwrap();
+                let _ = reply.send(signature);
+            }
+        });
+        Self { address, requests }
+    }
+}
+
+#[async_trait]
+impl Signer for RemoteSigner {
+    fn address(&self) -> &Bech32Address {
+        &self.address
+    }
+
+    async fn sign(&self, message: Message) -> Result<Signature> {
+        let (reply, response) = oneshot::channel();
+        self.requests
+            .send((message, reply))
+            .await
+            .map_err(|_| error!(InfrastructureError, "signing service is gone"))?;
+        response
+            .await
+            .map_err(|_| error!(InfrastructureError, "signing service dropped the request"))
+    }
+}
+
+// fuels can only sign transactions with a `SecretKey` it holds. To use any `Signer` the
+// builder signs with this throwaway key, which reserves the witness slots, and
+// `sign_transaction` swaps the real signatures in once the tx is built.
+const PLACEHOLDER_KEY: [u8; 32] = [1; 32];
+
+pub fn placeholder_key() -> SecretKey {
+    SecretKey::try_from(PLACEHOLDER_KEY.as_slice()).unwrap()
+}
+
+#[derive(Debug, Clone)]
+pub struct SignerAccount<S: Signer + Clone> {
+    signer: S,
+    provider: Provider,
+}
+
+impl<S: Signer + Clone> SignerAccount<S> {
+    pub fn new(signer: S, provider: Provider) -> Self {
+        Self { signer, provider }
+    }
+
+    pub fn signer(&self) -> &S {
+        &self.signer
+    }
+}
+
+#[async_trait]
+impl<S: Signer + Clone> ViewOnlyAccount for SignerAccount<S> {
+    fn address(&self) -> &Bech32Address {
+        self.signer.address()
+    }
+
+    fn try_provider(&self) -> Result<&Provider> {
+        Ok(&self.provider)
+    }
+}
+
+#[async_trait]
+impl<S: Signer + Clone> Account for SignerAccount<S> {
+    fn add_witnessses<Tb: TransactionBuilder>(&self, tb: &mut Tb) {
+        tb.add_unresolved_signature(self.address().clone(), placeholder_key());
+    }
+}
+
+#[async_trait]
+pub trait SparkAccount: Account {
+    // Last step before a built tx goes to the node, external signers sign it here
+    async fn finalize_tx(&self, tx: ScriptTransaction) -> Result<ScriptTransaction> {
+        Ok(tx)
+    }
+}
+
+impl SparkAccount for WalletUnlocked {}
+
+#[async_trait]
+impl<S: Signer + Clone> SparkAccount for SignerAccount<S> {
+    async fn finalize_tx(&self, tx: ScriptTransaction) -> Result<ScriptTransaction> {
+        sign_transaction(&self.signer, tx, self.provider.chain_id()).await
+    }
+}
+
+/// Puts `signer`'s signature into every witness slot its coins point at.
+pub async fn sign_transaction(
+    signer: &impl Signer,
+    tx: ScriptTransaction,
+    chain_id: ChainId,
+) -> Result<ScriptTransaction> {
+    let mut script: Script = tx.into();
+    let signature = signer
+        .sign(Message::from_bytes(*script.id(&chain_id)))
+        .await?;
+    let owner = Address::from(signer.address());
+
+    let witness_indexes: Vec<usize> = script
+        .inputs()
+        .iter()
+        .filter(|input| input.input_owner() == Some(&owner))
+        .filter_map(|input| input.witness_index())
+        .map(usize::from)
+        .collect();
+    if witness_indexes.is_empty() {
+        return Err(error!(
+            InvalidData,
+            "no inputs of {} to sign",
+            signer.address()
+        ));
+    }
+    for index in witness_indexes {
+        script.witnesses_mut()[index] = Witness::from(signature.as_ref().to_vec());
+    }
+    Ok(script.into())
+}
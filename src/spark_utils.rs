@@ -1,20 +1,44 @@
+use crate::auction::DutchAuction;
+use crate::ladder::PriceLadder;
+use crate::manifest::file_hash;
+use crate::network::{NetworkError, NetworkProfile};
+use crate::oco::OcoOrder;
+use crate::order::{validate_decimals, Expiry, OrderFee, OrderOptions, Price};
+use crate::rfq::SignedRfqQuote;
+use crate::signer::SparkAccount;
+use crate::wallet::{AccountError, Accounts};
 use fuels::accounts::predicate::Predicate;
 use fuels::accounts::wallet::WalletUnlocked;
+use fuels::accounts::ViewOnlyAccount;
+use fuels::core::codec::LogDecoder;
 use fuels::prelude::Account;
 use fuels::prelude::Bech32Address;
+use fuels::prelude::Bech32ContractId;
+use fuels::prelude::Provider;
 use fuels::prelude::TxPolicies;
 use fuels::programs::call_response::FuelCallResponse;
 use fuels::programs::call_utils::TxDependencyExtension;
 use fuels::programs::script_calls::ScriptCallHandler;
-use fuels::types::unresolved_bytes::UnresolvedBytes;
+use fuels::tx::{Output, Receipt, UtxoId};
+use fuels::types::coin::Coin;
+use fuels::types::coin_type::CoinType;
+use fuels::types::errors::error;
+use fuels::types::input::Input;
+use fuels::types::transaction::{ScriptTransaction, Transaction};
+use fuels::types::unresolved_bytes::{Data, UnresolvedBytes};
 use fuels::types::Address;
 use fuels::types::AssetId;
+use fuels::types::Bits256;
+use fuels::types::Bytes;
+use fuels::types::Bytes32;
 use fuels::types::ContractId;
+use fuels::types::Identity;
 use fuels::{
     prelude::abigen,
-    programs::contract::{CallParameters, Contract, LoadConfiguration},
+    programs::contract::{CallParameters, Contract, ContractCallHandler, LoadConfiguration},
 };
 use rand::Rng;
+use sha2::{Digest, Sha256};
 use src20_sdk::token_utils::Asset;
 use std::path::PathBuf;
 use std::str::FromStr;
@@ -27,6 +51,22 @@ abigen!(
         name = "SellPredicate",
         abi = "predicate-sell/out/debug/predicate-sell-abi.json"
     ),
+    Predicate(
+        name = "DutchAuctionPredicate",
+        abi = "predicate-dutch-auction/out/debug/predicate-dutch-auction-abi.json"
+    ),
+    Predicate(
+        name = "LadderPredicate",
+        abi = "predicate-ladder/out/debug/predicate-ladder-abi.json"
+    ),
+    Predicate(
+        name = "OcoPredicate",
+        abi = "predicate-oco/out/debug/predicate-oco-abi.json"
+    ),
+    Predicate(
+        name = "RfqPredicate",
+        abi = "predicate-rfq/out/debug/predicate-rfq-abi.json"
+    ),
     Contract(
         name = "ProxyContract",
         abi = "proxy-contract/out/debug/proxy-contract-abi.json"
@@ -36,65 +76,729 @@ abigen!(
 const PROXY_BIN_PATH: &str = "proxy-contract/out/debug/proxy-contract.bin";
 const PREDICATE_BUY_BIN_PATH: &str = "predicate-buy/out/debug/predicate-buy.bin";
 const PREDICATE_SELL_BIN_PATH: &str = "predicate-sell/out/debug/predicate-sell.bin";
+const PREDICATE_DUTCH_AUCTION_BIN_PATH: &str =
+    "predicate-dutch-auction/out/debug/predicate-dutch-auction.bin";
+const PREDICATE_LADDER_BIN_PATH: &str = "predicate-ladder/out/debug/predicate-ladder.bin";
+const PREDICATE_OCO_BIN_PATH: &str = "predicate-oco/out/debug/predicate-oco.bin";
+const PREDICATE_RFQ_BIN_PATH: &str = "predicate-rfq/out/debug/predicate-rfq.bin";
+const EXPIRY_SCRIPT_BIN_PATH: &str = "expiry-script/out/debug/expiry-script.bin";
+const PREDICATE_BUY_ABI_PATH: &str = "predicate-buy/out/debug/predicate-buy-abi.json";
+const PREDICATE_SELL_ABI_PATH: &str = "predicate-sell/out/debug/predicate-sell-abi.json";
+const PREDICATE_DUTCH_AUCTION_ABI_PATH: &str =
+    "predicate-dutch-auction/out/debug/predicate-dutch-auction-abi.json";
+const PREDICATE_LADDER_ABI_PATH: &str = "predicate-ladder/out/debug/predicate-ladder-abi.json";
+const PREDICATE_OCO_ABI_PATH: &str = "predicate-oco/out/debug/predicate-oco-abi.json";
+
+pub const PRICE_DECIMALS: u32 = 9;
+// Bumped whenever predicate-buy or predicate-sell change, the proxy logs it with every order
+pub const PREDICATE_VERSION: u64 = 2;
 
-pub struct Spark {
-    pub proxy: ProxyContract<WalletUnlocked>,
+/// A client of one market of the proxy, `with_market` switches to another.
+pub struct Spark<A: SparkAccount = WalletUnlocked> {
+    pub proxy: ProxyContract<A>,
+    market_id: u64,
+    market: MarketInfo,
 }
 
-impl Spark {
+impl MarketInfo {
+    /// The market `deploy_proxy` lists for these assets, with a tick and lot size of 1.
+    pub fn new(base_asset: &Asset, quote_asset: &Asset) -> Self {
+        Self {
+            base_asset: base_asset.asset_id,
+            quote_asset: quote_asset.asset_id,
+            base_asset_decimals: base_asset.decimals as u32,
+            quote_asset_decimals: quote_asset.decimals as u32,
+            price_decimals: PRICE_DECIMALS,
+            tick_size: 1,
+            lot_size: 1,
+        }
+    }
+
+    /// Scaled prices have to be a multiple of `tick_size`.
+    pub fn with_tick_size(mut self, tick_size: u64) -> Self {
+        self.tick_size = tick_size;
+        self
+    }
+
+    /// Sell orders have to be a multiple of `lot_size` of the base asset.
+    pub fn with_lot_size(mut self, lot_size: u64) -> Self {
+        self.lot_size = lot_size;
+        self
+    }
+}
+
+// Everything that doesn't need keys: predicates, balances. Backends and indexers can run
+// on this alone.
+#[derive(Debug, Clone)]
+pub struct SparkReader {
+    provider: Provider,
+    proxy_id: Bech32ContractId,
+}
+
+impl SparkReader {
+    pub fn new(provider: &Provider, proxy_id: impl Into<Bech32ContractId>) -> Self {
+        Self {
+            provider: provider.clone(),
+            proxy_id: proxy_id.into(),
+        }
+    }
+
+    pub fn proxy_id(&self) -> &Bech32ContractId {
+        &self.proxy_id
+    }
+
+    pub fn provider(&self) -> &Provider {
+        &self.provider
+    }
+
     pub fn get_buy_predicate(
         &self,
-        wallet: &WalletUnlocked,
-        base_asset: &Asset,
-        quote_asset: &Asset,
+        maker: &Bech32Address,
+        market: &MarketInfo,
         price: u64,
         min_fulfill_quote_amount: u64,
     ) -> Predicate {
-        let configurables = BuyPredicateConfigurables::new()
-            .with_QUOTE_ASSET(quote_asset.asset_id.into())
-            .with_BASE_ASSET(base_asset.asset_id.into())
-            .with_QUOTE_DECIMALS(quote_asset.decimals as u32)
-            .with_BASE_DECIMALS(base_asset.decimals as u32)
-            .with_MAKER(wallet.address().into())
+        let options = OrderOptions::default().with_min_fulfill_amount(min_fulfill_quote_amount);
+        self.get_buy_predicate_with_options(maker, market, price, &options)
+    }
+
+    pub fn get_buy_predicate_with_options(
+        &self,
+        maker: &Bech32Address,
+        market: &MarketInfo,
+        price: impl Into<Price>,
+        options: &OrderOptions,
+    ) -> Predicate {
+        let price: Price = price.into();
+        let (price, price_den) = price.to_configurables();
+        let mut configurables = BuyPredicateConfigurables::new()
+            .with_QUOTE_ASSET(market.quote_asset.into())
+            .with_BASE_ASSET(market.base_asset.into())
+            .with_QUOTE_DECIMALS(market.quote_asset_decimals)
+            .with_BASE_DECIMALS(market.base_asset_decimals)
+            .with_PRICE_DECIMALS(market.price_decimals)
+            .with_MAKER(maker.into())
             .with_PRICE(price)
-            .with_MIN_FULFILL_QUOTE_AMOUNT(min_fulfill_quote_amount);
+            .with_PRICE_DEN(price_den)
+            .with_MIN_FULFILL_QUOTE_AMOUNT(options.min_fulfill_amount)
+            .with_NONCE(options.nonce);
+        if let Some(recipient) = options.recipient {
+            configurables = configurables.with_RECIPIENT(recipient);
+        }
+        if let Some(taker) = options.allowed_taker {
+            configurables = configurables.with_ALLOWED_TAKER(taker);
+        }
+        if options.all_or_none {
+            configurables = configurables.with_ALL_OR_NONE(true);
+        }
+        if let Some(fee) = options.fee {
+            configurables = configurables
+                .with_FEE_RECIPIENT(fee.recipient)
+                .with_FEE_BPS(fee.bps);
+        }
+        if options.expiry != Expiry::Never {
+            configurables = configurables
+                .with_EXPIRY(options.expiry.to_configurable())
+                .with_EXPIRY_SCRIPT_HASH(expiry_script_hash());
+        }
 
         Predicate::load_from(PREDICATE_BUY_BIN_PATH)
             .unwrap()
             .with_configurables(configurables)
-            .with_provider(wallet.provider().unwrap().clone())
+            .with_provider(self.provider.clone())
     }
+
     pub fn get_sell_predicate(
         &self,
-        wallet: &WalletUnlocked,
-        base_asset: &Asset,
-        quote_asset: &Asset,
+        maker: &Bech32Address,
+        market: &MarketInfo,
         price: u64,
         min_fulfill_base_amount: u64,
     ) -> Predicate {
-        let configurables = SellPredicateConfigurables::new()
-            .with_QUOTE_ASSET(quote_asset.asset_id.into())
-            .with_BASE_ASSET(base_asset.asset_id.into())
-            .with_QUOTE_DECIMALS(quote_asset.decimals as u32)
-            .with_BASE_DECIMALS(base_asset.decimals as u32)
-            .with_MAKER(wallet.address().into())
+        let options = OrderOptions::default().with_min_fulfill_amount(min_fulfill_base_amount);
+        self.get_sell_predicate_with_options(maker, market, price, &options)
+    }
+
+    pub fn get_sell_predicate_with_options(
+        &self,
+        maker: &Bech32Address,
+        market: &MarketInfo,
+        price: impl Into<Price>,
+        options: &OrderOptions,
+    ) -> Predicate {
+        let price: Price = price.into();
+        let (price, price_den) = price.to_configurables();
+        let mut configurables = SellPredicateConfigurables::new()
+            .with_QUOTE_ASSET(market.quote_asset.into())
+            .with_BASE_ASSET(market.base_asset.into())
+            .with_QUOTE_DECIMALS(market.quote_asset_decimals)
+            .with_BASE_DECIMALS(market.base_asset_decimals)
+            .with_PRICE_DECIMALS(market.price_decimals)
+            .with_MAKER(maker.into())
             .with_PRICE(price)
-            .with_MIN_FULFILL_BASE_AMOUNT(min_fulfill_base_amount);
+            .with_PRICE_DEN(price_den)
+            .with_MIN_FULFILL_BASE_AMOUNT(options.min_fulfill_amount)
+            .with_NONCE(options.nonce);
+        if let Some(recipient) = options.recipient {
+            configurables = configurables.with_RECIPIENT(recipient);
+        }
+        if let Some(taker) = options.allowed_taker {
+            configurables = configurables.with_ALLOWED_TAKER(taker);
+        }
+        if options.all_or_none {
+            configurables = configurables.with_ALL_OR_NONE(true);
+        }
+        if let Some(fee) = options.fee {
+            configurables = configurables
+                .with_FEE_RECIPIENT(fee.recipient)
+                .with_FEE_BPS(fee.bps);
+        }
+        if options.expiry != Expiry::Never {
+            configurables = configurables
+                .with_EXPIRY(options.expiry.to_configurable())
+                .with_EXPIRY_SCRIPT_HASH(expiry_script_hash());
+        }
 
         Predicate::load_from(PREDICATE_SELL_BIN_PATH)
             .unwrap()
             .with_configurables(configurables)
-            .with_provider(wallet.provider().unwrap().clone())
+            .with_provider(self.provider.clone())
+    }
+
+    // The buy or sell predicate a `CreateOrderEvent` was logged for, its address is the event's
+    // `predicate_root` unless the maker funded some other predicate. None for events from another
+    // predicate version or a contract maker.
+    pub fn predicate_from_event(&self, event: &CreateOrderEvent) -> Option<Predicate> {
+        if event.predicate_version != PREDICATE_VERSION {
+            return None;
+        }
+        let Identity::Address(maker) = event.maker else {
+            return None;
+        };
+        let predicate = match event.order_type {
+            OrderType::BUY => {
+                let configurables = BuyPredicateConfigurables::new()
+                    .with_QUOTE_ASSET(event.quote_asset.into())
+                    .with_BASE_ASSET(event.base_asset.into())
+                    .with_QUOTE_DECIMALS(event.quote_asset_decimals)
+                    .with_BASE_DECIMALS(event.base_asset_decimals)
+                    .with_PRICE_DECIMALS(event.price_decimals)
+                    .with_MAKER(maker)
+                    .with_PRICE(event.price)
+                    .with_PRICE_DEN(event.price_den)
+                    .with_MIN_FULFILL_QUOTE_AMOUNT(event.min_fulfill_amount)
+                    .with_EXPIRY(event.expiry)
+                    .with_EXPIRY_SCRIPT_HASH(event.expiry_script_hash)
+                    .with_NONCE(event.nonce)
+                    .with_FEE_RECIPIENT(event.fee_recipient)
+                    .with_FEE_BPS(event.fee_bps)
+                    .with_RECIPIENT(event.recipient)
+                    .with_ALLOWED_TAKER(event.allowed_taker)
+                    .with_ALL_OR_NONE(event.all_or_none);
+                Predicate::load_from(PREDICATE_BUY_BIN_PATH)
+                    .unwrap()
+                    .with_configurables(configurables)
+            }
+            OrderType::SELL => {
+                let configurables = SellPredicateConfigurables::new()
+                    .with_QUOTE_ASSET(event.quote_asset.into())
+                    .with_BASE_ASSET(event.base_asset.into())
+                    .with_QUOTE_DECIMALS(event.quote_asset_decimals)
+                    .with_BASE_DECIMALS(event.base_asset_decimals)
+                    .with_PRICE_DECIMALS(event.price_decimals)
+                    .with_MAKER(maker)
+                    .with_PRICE(event.price)
+                    .with_PRICE_DEN(event.price_den)
+                    .with_MIN_FULFILL_BASE_AMOUNT(event.min_fulfill_amount)
+                    .with_EXPIRY(event.expiry)
+                    .with_EXPIRY_SCRIPT_HASH(event.expiry_script_hash)
+                    .with_NONCE(event.nonce)
+                    .with_FEE_RECIPIENT(event.fee_recipient)
+                    .with_FEE_BPS(event.fee_bps)
+                    .with_RECIPIENT(event.recipient)
+                    .with_ALLOWED_TAKER(event.allowed_taker)
+                    .with_ALL_OR_NONE(event.all_or_none);
+                Predicate::load_from(PREDICATE_SELL_BIN_PATH)
+                    .unwrap()
+                    .with_configurables(configurables)
+            }
+        };
+
+        Some(predicate.with_provider(self.provider.clone()))
+    }
+
+    pub fn get_dutch_auction_predicate(
+        &self,
+        maker: &Bech32Address,
+        market: &MarketInfo,
+        auction: &DutchAuction,
+        nonce: Bits256,
+    ) -> Predicate {
+        let configurables = DutchAuctionPredicateConfigurables::new()
+            .with_QUOTE_ASSET(market.quote_asset.into())
+            .with_BASE_ASSET(market.base_asset.into())
+            .with_QUOTE_DECIMALS(market.quote_asset_decimals)
+            .with_BASE_DECIMALS(market.base_asset_decimals)
+            .with_PRICE_DECIMALS(market.price_decimals)
+            .with_MAKER(maker.into())
+            .with_START_PRICE(auction.start_price)
+            .with_END_PRICE(auction.end_price)
+            .with_START_BLOCK(auction.start_block)
+            .with_END_BLOCK(auction.end_block)
+            .with_NONCE(nonce);
+
+        Predicate::load_from(PREDICATE_DUTCH_AUCTION_BIN_PATH)
+            .unwrap()
+            .with_configurables(configurables)
+            .with_provider(self.provider.clone())
+    }
+
+    pub fn get_ladder_predicate(
+        &self,
+        maker: &Bech32Address,
+        market: &MarketInfo,
+        ladder: &PriceLadder,
+        nonce: Bits256,
+    ) -> Predicate {
+        let (prices, sizes, levels) = ladder.to_configurables();
+        let configurables = LadderPredicateConfigurables::new()
+            .with_QUOTE_ASSET(market.quote_asset.into())
+            .with_BASE_ASSET(market.base_asset.into())
+            .with_QUOTE_DECIMALS(market.quote_asset_decimals)
+            .with_BASE_DECIMALS(market.base_asset_decimals)
+            .with_PRICE_DECIMALS(market.price_decimals)
+            .with_MAKER(maker.into())
+            .with_PRICES(prices)
+            .with_SIZES(sizes)
+            .with_LEVELS(levels)
+            .with_NONCE(nonce);
+
+        Predicate::load_from(PREDICATE_LADDER_BIN_PATH)
+            .unwrap()
+            .with_configurables(configurables)
+            .with_provider(self.provider.clone())
+    }
+
+    /// Quote a fill of `base_amount` from the ladder at `predicate_root` costs right now, None
+    /// if there isn't that much left. Priced by the coin `ladder_coin` picks.
+    pub async fn ladder_fill_quote(
+        &self,
+        predicate_root: &Bech32Address,
+        ladder: &PriceLadder,
+        market: &MarketInfo,
+        base_amount: u64,
+    ) -> Result<Option<u64>, fuels::prelude::Error> {
+        let Some(coin) = self.ladder_coin(predicate_root, market.base_asset).await? else {
+            return Ok(None);
+        };
+        Ok(ladder.quote_amount(
+            coin.amount,
+            base_amount,
+            market.base_asset_decimals,
+            market.quote_asset_decimals,
+        ))
+    }
+
+    /// The biggest coin of a ladder, the one fills spend. Ladder fills spend a single coin of
+    /// the predicate and are priced by it, coins sent to a ladder on top of its own only sell
+    /// at higher levels.
+    pub async fn ladder_coin(
+        &self,
+        predicate_root: &Bech32Address,
+        base_asset: AssetId,
+    ) -> Result<Option<Coin>, fuels::prelude::Error> {
+        let coins = self.provider.get_coins(predicate_root, base_asset).await?;
+        Ok(coins.into_iter().max_by_key(|coin| coin.amount))
+    }
+
+    pub fn get_oco_predicate(
+        &self,
+        maker: &Bech32Address,
+        market: &MarketInfo,
+        oco: &OcoOrder,
+        nonce: Bits256,
+    ) -> Predicate {
+        let configurables = OcoPredicateConfigurables::new()
+            .with_QUOTE_ASSET(market.quote_asset.into())
+            .with_BASE_ASSET(market.base_asset.into())
+            .with_QUOTE_DECIMALS(market.quote_asset_decimals)
+            .with_BASE_DECIMALS(market.base_asset_decimals)
+            .with_PRICE_DECIMALS(market.price_decimals)
+            .with_MAKER(maker.into())
+            .with_TAKE_PROFIT_PRICE(oco.take_profit_price)
+            .with_STOP_PRICE(oco.stop_price)
+            .with_STOP_KEEPER(oco.stop_keeper)
+            .with_NONCE(nonce);
+
+        Predicate::load_from(PREDICATE_OCO_BIN_PATH)
+            .unwrap()
+            .with_configurables(configurables)
+            .with_provider(self.provider.clone())
+    }
+
+    /// The maker's RFQ predicate for one side of the market: asks hold the base asset, bids
+    /// (`buy`) the quote asset. Fund it once, then stream `SignedRfqQuote`s.
+    pub fn get_rfq_predicate(
+        &self,
+        maker: &Bech32Address,
+        market: &MarketInfo,
+        buy: bool,
+    ) -> Predicate {
+        let configurables = RfqPredicateConfigurables::new()
+            .with_QUOTE_ASSET(market.quote_asset.into())
+            .with_BASE_ASSET(market.base_asset.into())
+            .with_QUOTE_DECIMALS(market.quote_asset_decimals)
+            .with_BASE_DECIMALS(market.base_asset_decimals)
+            .with_PRICE_DECIMALS(market.price_decimals)
+            .with_MAKER(maker.into())
+            .with_BUY(buy)
+            .with_EXPIRY_SCRIPT_HASH(expiry_script_hash());
+
+        Predicate::load_from(PREDICATE_RFQ_BIN_PATH)
+            .unwrap()
+            .with_configurables(configurables)
+            .with_provider(self.provider.clone())
+    }
+
+    pub async fn order_balance(
+        &self,
+        predicate_root: &Bech32Address,
+        asset: AssetId,
+    ) -> Result<u64, fuels::prelude::Error> {
+        self.provider.get_asset_balance(predicate_root, asset).await
+    }
+
+    /// The biggest coin of an RFQ predicate, the one to sign the next quotes for.
+    pub async fn rfq_coin(
+        &self,
+        predicate_root: &Bech32Address,
+        asset: AssetId,
+    ) -> Result<Option<UtxoId>, fuels::prelude::Error> {
+        let coins = self.provider.get_coins(predicate_root, asset).await?;
+        Ok(coins
+            .into_iter()
+            .max_by_key(|coin| coin.amount)
+            .map(|coin| coin.utxo_id))
+    }
+
+    /// The `FillEvent`s this proxy logged in `receipts`.
+    pub fn decode_fill_events(
+        &self,
+        receipts: &[Receipt],
+    ) -> Result<Vec<FillEvent>, fuels::prelude::Error> {
+        self.log_decoder()
+            .decode_logs_with_type::<FillEvent>(receipts)
+    }
+
+    /// The `FillEvent`s of a committed tx.
+    pub async fn fill_events(
+        &self,
+        tx_id: &Bytes32,
+    ) -> Result<Vec<FillEvent>, fuels::prelude::Error> {
+        self.decode_fill_events(&self.tx_receipts(tx_id).await?)
+    }
+
+    /// The `CancelOrderEvent`s this proxy logged in `receipts`.
+    pub fn decode_cancel_events(
+        &self,
+        receipts: &[Receipt],
+    ) -> Result<Vec<CancelOrderEvent>, fuels::prelude::Error> {
+        self.log_decoder()
+            .decode_logs_with_type::<CancelOrderEvent>(receipts)
+    }
+
+    /// The `CancelOrderEvent`s of a committed tx.
+    pub async fn cancel_events(
+        &self,
+        tx_id: &Bytes32,
+    ) -> Result<Vec<CancelOrderEvent>, fuels::prelude::Error> {
+        self.decode_cancel_events(&self.tx_receipts(tx_id).await?)
+    }
+
+    fn log_decoder(&self) -> LogDecoder {
+        // decoding only needs the contract id, the wallet never signs
+        ProxyContract::new(
+            self.proxy_id.clone(),
+            WalletUnlocked::new_random(Some(self.provider.clone())),
+        )
+        .log_decoder()
+    }
+
+    async fn tx_receipts(&self, tx_id: &Bytes32) -> Result<Vec<Receipt>, fuels::prelude::Error> {
+        self.provider
+            .tx_status(tx_id)
+            .await?
+            .take_receipts_checked(None)
+    }
+}
+
+impl<A: SparkAccount> Spark<A> {
+    pub fn reader(&self) -> SparkReader {
+        let account = self.proxy.account();
+        SparkReader::new(account.try_provider().unwrap(), self.proxy.contract_id())
+    }
+
+    /// The market orders are created in, see `markets` for its place in the registry.
+    pub fn market_id(&self) -> u64 {
+        self.market_id
+    }
+
+    /// The assets and decimals of the market, fetched once when `Spark` is built.
+    pub fn market(&self) -> &MarketInfo {
+        &self.market
+    }
+
+    /// Every market the proxy lists, a market's id is its index.
+    pub async fn markets(&self) -> Result<Vec<MarketInfo>, fuels::types::errors::Error> {
+        let count = self.proxy.methods().market_count().simulate().await?.value;
+        let mut markets = vec![];
+        for market_id in 0..count {
+            let market = self
+                .proxy
+                .methods()
+                .market_info(market_id)
+                .simulate()
+                .await?
+                .value;
+            markets.push(market);
+        }
+        Ok(markets)
+    }
+
+    /// The same client for another market of the proxy.
+    pub async fn with_market(&self, market_id: u64) -> Result<Self, fuels::types::errors::Error> {
+        let market = self
+            .proxy
+            .methods()
+            .market_info(market_id)
+            .simulate()
+            .await?
+            .value;
+        Ok(Self {
+            proxy: self.proxy.clone(),
+            market_id,
+            market,
+        })
+    }
+
+    /// Adds `market` to the proxy, only the account that deployed it can. The response value
+    /// is the new market's id.
+    pub async fn list_market(
+        &self,
+        market: &MarketInfo,
+    ) -> Result<FuelCallResponse<u64>, fuels::types::errors::Error> {
+        validate_decimals(
+            market.price_decimals,
+            market.base_asset_decimals,
+            market.quote_asset_decimals,
+        )
+        .map_err(|e| error!(InvalidData, "{e}"))?;
+        let call = self
+            .proxy
+            .methods()
+            .list_market(
+                market.base_asset,
+                market.quote_asset,
+                market.base_asset_decimals,
+                market.quote_asset_decimals,
+                market.tick_size,
+                market.lot_size,
+            )
+            .with_tx_policies(TxPolicies::default().with_gas_price(1));
+        let (tx_id, receipts) = submit(&self.proxy.account(), call.build_tx().await?).await?;
+        let mut response = call.get_response(receipts)?;
+        response.tx_id = Some(tx_id);
+        Ok(response)
+    }
+
+    fn maker_reader(&self, wallet: &impl ViewOnlyAccount) -> SparkReader {
+        SparkReader::new(wallet.try_provider().unwrap(), self.proxy.contract_id())
+    }
+
+    pub fn get_buy_predicate(
+        &self,
+        wallet: &impl ViewOnlyAccount,
+        price: u64,
+        min_fulfill_quote_amount: u64,
+    ) -> Predicate {
+        self.maker_reader(wallet).get_buy_predicate(
+            wallet.address(),
+            &self.market,
+            price,
+            min_fulfill_quote_amount,
+        )
+    }
+
+    pub fn get_buy_predicate_with_options(
+        &self,
+        wallet: &impl ViewOnlyAccount,
+        price: impl Into<Price>,
+        options: &OrderOptions,
+    ) -> Predicate {
+        self.maker_reader(wallet).get_buy_predicate_with_options(
+            wallet.address(),
+            &self.market,
+            price,
+            options,
+        )
+    }
+
+    pub fn get_sell_predicate(
+        &self,
+        wallet: &impl ViewOnlyAccount,
+        price: u64,
+        min_fulfill_base_amount: u64,
+    ) -> Predicate {
+        self.maker_reader(wallet).get_sell_predicate(
+            wallet.address(),
+            &self.market,
+            price,
+            min_fulfill_base_amount,
+        )
+    }
+
+    pub fn get_sell_predicate_with_options(
+        &self,
+        wallet: &impl ViewOnlyAccount,
+        price: impl Into<Price>,
+        options: &OrderOptions,
+    ) -> Predicate {
+        self.maker_reader(wallet).get_sell_predicate_with_options(
+            wallet.address(),
+            &self.market,
+            price,
+            options,
+        )
+    }
+
+    pub fn get_dutch_auction_predicate(
+        &self,
+        wallet: &impl ViewOnlyAccount,
+        auction: &DutchAuction,
+        nonce: Bits256,
+    ) -> Predicate {
+        self.maker_reader(wallet).get_dutch_auction_predicate(
+            wallet.address(),
+            &self.market,
+            auction,
+            nonce,
+        )
+    }
+
+    pub fn get_ladder_predicate(
+        &self,
+        wallet: &impl ViewOnlyAccount,
+        ladder: &PriceLadder,
+        nonce: Bits256,
+    ) -> Predicate {
+        self.maker_reader(wallet).get_ladder_predicate(
+            wallet.address(),
+            &self.market,
+            ladder,
+            nonce,
+        )
     }
 
-    pub async fn cancel_order(
+    pub fn get_oco_predicate(
         &self,
-        wallet: &WalletUnlocked,
+        wallet: &impl ViewOnlyAccount,
+        oco: &OcoOrder,
+        nonce: Bits256,
+    ) -> Predicate {
+        self.maker_reader(wallet)
+            .get_oco_predicate(wallet.address(), &self.market, oco, nonce)
+    }
+
+    pub fn get_rfq_predicate(&self, wallet: &impl ViewOnlyAccount, buy: bool) -> Predicate {
+        self.maker_reader(wallet)
+            .get_rfq_predicate(wallet.address(), &self.market, buy)
+    }
+
+    /// Takes `amount0` of the order's coins back to the maker (`wallet`) and calls the proxy's
+    /// `cancel_order` in the same tx, which logs a `CancelOrderEvent`.
+    pub async fn cancel_order<W: SparkAccount>(
+        &self,
+        wallet: &W,
         predicate: &Predicate,
         asset0: AssetId,
         amount0: u64,
     ) -> Result<FuelCallResponse<()>, fuels::prelude::Error> {
-        let provider = wallet.provider().unwrap();
+        let call = self
+            .cancel_order_call(wallet, predicate, asset0, amount0, &[])
+            .await?;
+        let (tx_id, receipts) = submit(wallet, call.build_tx().await?).await?;
+        let mut response = call.get_response(receipts)?;
+        response.tx_id = Some(tx_id);
+        Ok(response)
+    }
+
+    /// `cancel_order` in a tx that also has inputs of `contract_ids`, for batching the cancel
+    /// with calls to them. The proxy still logs the `CancelOrderEvent`.
+    pub async fn cancel_order_with_contracts<W: SparkAccount>(
+        &self,
+        wallet: &W,
+        predicate: &Predicate,
+        asset0: AssetId,
+        amount0: u64,
+        contract_ids: &[Bech32ContractId],
+    ) -> Result<FuelCallResponse<()>, fuels::prelude::Error> {
+        let call = self
+            .cancel_order_call(wallet, predicate, asset0, amount0, contract_ids)
+            .await?;
+        let (tx_id, receipts) = submit(wallet, call.build_tx().await?).await?;
+        let mut response = call.get_response(receipts)?;
+        response.tx_id = Some(tx_id);
+        Ok(response)
+    }
+
+    pub async fn build_cancel_order<W: SparkAccount>(
+        &self,
+        wallet: &W,
+        predicate: &Predicate,
+        asset0: AssetId,
+        amount0: u64,
+    ) -> Result<ScriptTransaction, fuels::prelude::Error> {
+        self.cancel_order_call(wallet, predicate, asset0, amount0, &[])
+            .await?
+            .build_tx()
+            .await
+    }
+
+    async fn cancel_order_call<W: SparkAccount>(
+        &self,
+        wallet: &W,
+        predicate: &Predicate,
+        asset0: AssetId,
+        amount0: u64,
+        contract_ids: &[Bech32ContractId],
+    ) -> Result<ContractCallHandler<W, ()>, fuels::prelude::Error> {
+        let (inputs, outputs) = self
+            .cancel_inputs_outputs(wallet, predicate, asset0, amount0)
+            .await?;
+        // Custom inputs and outputs go before the contract's, the predicate layout stays put
+        let call = ProxyContract::new(self.proxy.contract_id().clone(), wallet.clone())
+            .methods()
+            .cancel_order(
+                self.market_id,
+                Address::from(predicate.address()),
+                Address::from(wallet.address()),
+            )
+            .with_contract_ids(contract_ids)
+            .with_inputs(inputs)
+            .with_outputs(outputs)
+            .with_tx_policies(TxPolicies::default().with_gas_price(1));
+
+        Ok(call)
+    }
+
+    async fn cancel_inputs_outputs<W: SparkAccount>(
+        &self,
+        wallet: &W,
+        predicate: &Predicate,
+        asset0: AssetId,
+        amount0: u64,
+    ) -> Result<(Vec<Input>, Vec<Output>), fuels::prelude::Error> {
+        let provider = wallet.try_provider()?;
         let mut predicate = predicate.clone();
         predicate.set_provider(provider.clone());
 
@@ -102,18 +806,144 @@ impl Spark {
 
         let mut inputs_predicate = predicate
             .get_asset_inputs_for_amount(asset0, amount0)
-            .await
-            .unwrap();
+            .await?;
         inputs.append(&mut inputs_predicate);
 
         let mut outputs = vec![];
         let mut output_to_maker = wallet.get_asset_outputs_for_amount(wallet.address(), asset0, 0);
         outputs.append(&mut output_to_maker);
-        // println!("inputs = {:?}", inputs);
-        // println!("outputs = {:?}", outputs);
+        Ok((inputs, outputs))
+    }
+
+    /// Fills a buy or sell order with the fee, recipient, expiry and other options the predicate
+    /// was built with, read from its bytecode.
+    pub async fn fulfill_order<W: SparkAccount>(
+        &self,
+        wallet: &W,
+        predicate: &Predicate,
+        maker_address: &Bech32Address,
+        asset0: AssetId,
+        amount0: u64,
+        asset1: AssetId,
+        amount1: u64,
+    ) -> Result<FuelCallResponse<()>, fuels::prelude::Error> {
+        // The predicate holds the asset it offers: base for sell orders, quote for buy orders
+        let order_type = if asset0 == self.market.base_asset {
+            OrderType::SELL
+        } else {
+            OrderType::BUY
+        };
+        let options = predicate_options(predicate, &order_type);
+        self.fulfill_order_with_options(
+            wallet,
+            predicate,
+            maker_address,
+            asset0,
+            amount0,
+            asset1,
+            amount1,
+            &options,
+        )
+        .await
+    }
+
+    /// Fills an order created with `options`, expiring orders go through the expiry script.
+    pub async fn fulfill_order_with_options<W: SparkAccount>(
+        &self,
+        wallet: &W,
+        predicate: &Predicate,
+        maker_address: &Bech32Address,
+        asset0: AssetId,
+        amount0: u64,
+        asset1: AssetId,
+        amount1: u64,
+        options: &OrderOptions,
+    ) -> Result<FuelCallResponse<()>, fuels::prelude::Error> {
+        if options.expiry.is_expired_on(wallet.try_provider()?).await? {
+            return Err(error!(InvalidData, "order expired {:?}", options.expiry));
+        }
+        let script_call = self
+            .fulfill_order_call(
+                wallet,
+                predicate,
+                maker_address,
+                asset0,
+                amount0,
+                asset1,
+                amount1,
+                options,
+                None,
+            )
+            .await?;
+        send_script_call(wallet, script_call).await
+    }
+
+    pub async fn build_fulfill_order<W: SparkAccount>(
+        &self,
+        wallet: &W,
+        predicate: &Predicate,
+        maker_address: &Bech32Address,
+        asset0: AssetId,
+        amount0: u64,
+        asset1: AssetId,
+        amount1: u64,
+        options: &OrderOptions,
+    ) -> Result<ScriptTransaction, fuels::prelude::Error> {
+        self.fulfill_order_call(
+            wallet,
+            predicate,
+            maker_address,
+            asset0,
+            amount0,
+            asset1,
+            amount1,
+            options,
+            None,
+        )
+        .await?
+        .build_tx()
+        .await
+    }
+
+    async fn fulfill_order_call<W: SparkAccount>(
+        &self,
+        wallet: &W,
+        predicate: &Predicate,
+        maker_address: &Bech32Address,
+        asset0: AssetId,
+        amount0: u64,
+        asset1: AssetId,
+        amount1: u64,
+        options: &OrderOptions,
+        coin: Option<UtxoId>,
+    ) -> Result<ScriptCallHandler<W, ()>, fuels::prelude::Error> {
+        let provider = wallet.try_provider()?;
+        let (inputs, outputs) = self
+            .fill_inputs_outputs(
+                wallet,
+                predicate,
+                maker_address,
+                asset0,
+                amount0,
+                asset1,
+                amount1,
+                options,
+                coin,
+            )
+            .await?;
+
+        let (script, script_data) = match options.expiry {
+            Expiry::Never => (vec![], UnresolvedBytes::default()),
+            expiry => (
+                expiry_script_bytecode(),
+                UnresolvedBytes::new(vec![Data::Inline(
+                    expiry.to_configurable().to_be_bytes().to_vec(),
+                )]),
+            ),
+        };
         let script_call = ScriptCallHandler::new(
-            vec![],
-            UnresolvedBytes::default(),
+            script,
+            script_data,
             wallet.clone(),
             provider.clone(),
             Default::default(),
@@ -122,40 +952,54 @@ impl Spark {
         .with_outputs(outputs)
         .with_tx_policies(TxPolicies::default().with_gas_price(1));
 
-        script_call.call().await
+        Ok(script_call)
     }
 
-    pub async fn fulfill_order(
+    // The fill layout the predicates check: [0] asked coin to the maker (or the recipient),
+    // [1] taker change, [2] offered coin to the taker, [3] predicate change, [4] fee
+    async fn fill_inputs_outputs<W: SparkAccount>(
         &self,
-        wallet: &WalletUnlocked,
+        wallet: &W,
         predicate: &Predicate,
         maker_address: &Bech32Address,
         asset0: AssetId,
         amount0: u64,
         asset1: AssetId,
         amount1: u64,
-    ) -> Result<FuelCallResponse<()>, fuels::prelude::Error> {
-        let provider = wallet.provider().unwrap();
+        options: &OrderOptions,
+        coin: Option<UtxoId>,
+    ) -> Result<(Vec<Input>, Vec<Output>), fuels::prelude::Error> {
+        let provider = wallet.try_provider()?;
         let mut predicate = predicate.clone();
         predicate.set_provider(provider.clone());
 
         let mut inputs = vec![];
         // let balance = predicate.get_asset_balance(&asset0).await.unwrap_or(0);
-        let mut inputs_predicate = predicate
-            .get_asset_inputs_for_amount(asset0, 1)
-            .await
-            .unwrap();
+        // all-or-none orders have to spend every coin they hold
+        let predicate_amount = if options.all_or_none { amount0 } else { 1 };
+        let mut inputs_predicate = match coin {
+            // an RFQ quote is for one coin, it goes first
+            Some(utxo_id) => vec![predicate_coin_input(&predicate, asset0, utxo_id).await?],
+            None => {
+                predicate
+                    .get_asset_inputs_for_amount(asset0, predicate_amount)
+                    .await?
+            }
+        };
         inputs.append(&mut inputs_predicate);
+        let fee_amount = options.fee.map_or(0, |fee| fee.fee_for(amount1));
         let mut inputs_from_taker = wallet
-            .get_asset_inputs_for_amount(asset1, amount1)
-            .await
-            .unwrap();
+            .get_asset_inputs_for_amount(asset1, amount1 + fee_amount)
+            .await?;
         inputs.append(&mut inputs_from_taker);
 
         // Output for the asked coin transferred from the taker to the receiver
+        let receiver = options
+            .recipient
+            .map(Bech32Address::from)
+            .unwrap_or(maker_address.clone());
         let mut outputs = vec![];
-        let mut output_to_maker =
-            wallet.get_asset_outputs_for_amount(maker_address, asset1, amount1);
+        let mut output_to_maker = wallet.get_asset_outputs_for_amount(&receiver, asset1, amount1);
         outputs.append(&mut output_to_maker);
 
         // Output for the offered coin transferred from the predicate to the order taker
@@ -163,6 +1007,11 @@ impl Spark {
             predicate.get_asset_outputs_for_amount(wallet.address(), asset0, amount0);
         outputs.append(&mut output_to_taker);
 
+        // Fee output, the predicates expect it right after the predicate change
+        if let Some(fee) = options.fee {
+            outputs.push(Output::coin(fee.recipient, fee_amount, asset1));
+        }
+
         // Change output for unspent asked asset
         // let output_asked_change =
         //     wallet.get_asset_outputs_for_amount(wallet.address(), asset1, 0)[1];
@@ -179,6 +1028,221 @@ impl Spark {
         //     outputs.push(partial_fulfill_output);
         // }
 
+        Ok((inputs, outputs))
+    }
+
+    /// `fulfill_order_with_options` with a call to the proxy's `fulfill_order` in the fill tx,
+    /// which logs a `FillEvent` so indexers don't have to scan every tx for fills. The call
+    /// returns what the spent predicate coins give back as change. Expiring orders only take
+    /// fills that run the expiry script, they can't be filled this way.
+    pub async fn fulfill_order_via_proxy<W: SparkAccount>(
+        &self,
+        wallet: &W,
+        predicate: &Predicate,
+        maker_address: &Bech32Address,
+        asset0: AssetId,
+        amount0: u64,
+        asset1: AssetId,
+        amount1: u64,
+        options: &OrderOptions,
+    ) -> Result<FuelCallResponse<u64>, fuels::prelude::Error> {
+        if options.expiry != Expiry::Never {
+            return Err(error!(
+                InvalidData,
+                "expiring orders can't be filled through the proxy"
+            ));
+        }
+        let (inputs, outputs) = self
+            .fill_inputs_outputs(
+                wallet,
+                predicate,
+                maker_address,
+                asset0,
+                amount0,
+                asset1,
+                amount1,
+                options,
+                None,
+            )
+            .await?;
+        // Custom inputs and outputs go before the contract's, the predicate layout stays put
+        let call = ProxyContract::new(self.proxy.contract_id().clone(), wallet.clone())
+            .methods()
+            .fulfill_order(self.market_id, Address::from(predicate.address()))
+            .with_inputs(inputs)
+            .with_outputs(outputs)
+            .with_tx_policies(TxPolicies::default().with_gas_price(1));
+        let (tx_id, receipts) = submit(wallet, call.build_tx().await?).await?;
+        let mut response = call.get_response(receipts)?;
+        response.tx_id = Some(tx_id);
+        Ok(response)
+    }
+
+    /// Fills a dutch auction at the price of `maturity`, the tx can't land before that block.
+    /// `quote_amount` should be at least `DutchAuction::quote_amount_at(base_amount, maturity, ..)`.
+    pub async fn fulfill_auction<W: SparkAccount>(
+        &self,
+        wallet: &W,
+        predicate: &Predicate,
+        maker_address: &Bech32Address,
+        base_asset: AssetId,
+        base_amount: u64,
+        quote_asset: AssetId,
+        quote_amount: u64,
+        maturity: u32,
+    ) -> Result<FuelCallResponse<()>, fuels::prelude::Error> {
+        let script_call = self
+            .fulfill_order_call(
+                wallet,
+                predicate,
+                maker_address,
+                base_asset,
+                base_amount,
+                quote_asset,
+                quote_amount,
+                &OrderOptions::default(),
+                None,
+            )
+            .await?
+            .with_tx_policies(
+                TxPolicies::default()
+                    .with_gas_price(1)
+                    .with_maturity(maturity),
+            );
+        send_script_call(wallet, script_call).await
+    }
+
+    /// Buys `base_amount` off a ladder, see `SparkReader::ladder_fill_quote` for `quote_amount`.
+    /// The fill spends the coin `SparkReader::ladder_coin` picks and the predicate prices it by
+    /// that coin alone.
+    pub async fn fulfill_ladder<W: SparkAccount>(
+        &self,
+        wallet: &W,
+        predicate: &Predicate,
+        maker_address: &Bech32Address,
+        base_asset: AssetId,
+        base_amount: u64,
+        quote_asset: AssetId,
+        quote_amount: u64,
+    ) -> Result<FuelCallResponse<()>, fuels::prelude::Error> {
+        let coin = self
+            .reader()
+            .ladder_coin(predicate.address(), base_asset)
+            .await?
+            .ok_or_else(|| error!(InvalidData, "the ladder has no coins left"))?;
+        let script_call = self
+            .fulfill_order_call(
+                wallet,
+                predicate,
+                maker_address,
+                base_asset,
+                base_amount,
+                quote_asset,
+                quote_amount,
+                &OrderOptions::default(),
+                Some(coin.utxo_id),
+            )
+            .await?;
+        send_script_call(wallet, script_call).await
+    }
+
+    /// Takes the whole OCO order, `quote_amount` decides the leg (see `OcoOrder::quote_amount`),
+    /// the stop leg only goes through when `wallet` is the stop keeper.
+    pub async fn fulfill_oco<W: SparkAccount>(
+        &self,
+        wallet: &W,
+        predicate: &Predicate,
+        maker_address: &Bech32Address,
+        base_asset: AssetId,
+        base_amount: u64,
+        quote_asset: AssetId,
+        quote_amount: u64,
+    ) -> Result<FuelCallResponse<()>, fuels::prelude::Error> {
+        let options = OrderOptions::default().with_all_or_none();
+        let script_call = self
+            .fulfill_order_call(
+                wallet,
+                predicate,
+                maker_address,
+                base_asset,
+                base_amount,
+                quote_asset,
+                quote_amount,
+                &options,
+                None,
+            )
+            .await?;
+        send_script_call(wallet, script_call).await
+    }
+
+    /// Takes `amount0` of the predicate's asset at a maker's signed quote, paying `amount1`.
+    pub async fn fulfill_rfq<W: SparkAccount>(
+        &self,
+        wallet: &W,
+        predicate: &Predicate,
+        maker_address: &Bech32Address,
+        signed_quote: &SignedRfqQuote,
+        asset0: AssetId,
+        amount0: u64,
+        asset1: AssetId,
+        amount1: u64,
+    ) -> Result<FuelCallResponse<()>, fuels::prelude::Error> {
+        let quote = signed_quote.quote;
+        if amount0 > quote.max_size {
+            return Err(error!(
+                InvalidData,
+                "{amount0} is over the quote's max size {}", quote.max_size
+            ));
+        }
+        let options = OrderOptions::default().with_expiry(quote.expiry);
+        if options.expiry.is_expired_on(wallet.try_provider()?).await? {
+            return Err(error!(InvalidData, "quote expired {:?}", quote.expiry));
+        }
+        let data = RfqPredicateEncoder::encode_data(
+            quote.price,
+            quote.max_size,
+            quote.expiry.to_configurable(),
+            quote.nonce,
+            signed_quote.signature_b512(),
+        );
+        let predicate = predicate.clone().with_data(data);
+        let script_call = self
+            .fulfill_order_call(
+                wallet,
+                &predicate,
+                maker_address,
+                asset0,
+                amount0,
+                asset1,
+                amount1,
+                &options,
+                Some(quote.coin),
+            )
+            .await?;
+        send_script_call(wallet, script_call).await
+    }
+
+    /// Moves the coins of an RFQ predicate back into it as one new coin, so every quote signed
+    /// for the old coins stops working. Only the maker (`wallet`) can.
+    pub async fn revoke_rfq_quotes<W: SparkAccount>(
+        &self,
+        wallet: &W,
+        predicate: &Predicate,
+        asset: AssetId,
+    ) -> Result<FuelCallResponse<()>, fuels::prelude::Error> {
+        let provider = wallet.try_provider()?;
+        let mut predicate = predicate.clone();
+        predicate.set_provider(provider.clone());
+
+        let balance = predicate.get_asset_balance(&asset).await?;
+        let inputs = predicate
+            .get_asset_inputs_for_amount(asset, balance)
+            .await?;
+        let outputs = vec![Output::coin(
+            Address::from(predicate.address()),
+            balance,
+            asset,
+        )];
         let script_call = ScriptCallHandler::new(
             vec![],
             UnresolvedBytes::default(),
@@ -189,58 +1253,340 @@ impl Spark {
         .with_inputs(inputs)
         .with_outputs(outputs)
         .with_tx_policies(TxPolicies::default().with_gas_price(1));
-
-        script_call.call().await
+        send_script_call(wallet, script_call).await
     }
 
+    /// Creates an order whose predicate came from `get_buy_predicate`/`get_sell_predicate`, with
+    /// the same `min_fulfill_amount`, so the logged terms match the predicate.
     pub async fn create_order(
         &self,
         predicate_root: Address,
         payment_asset: AssetId,
         payment_size: u64,
         base_price: u64,
-    ) -> Result<FuelCallResponse<()>, fuels::types::errors::Error> {
+        min_fulfill_amount: u64,
+    ) -> Result<FuelCallResponse<Bits256>, fuels::types::errors::Error> {
+        let options = OrderOptions::default().with_min_fulfill_amount(min_fulfill_amount);
+        self.create_order_with_options(
+            predicate_root,
+            payment_asset,
+            payment_size,
+            base_price,
+            &options,
+        )
+        .await
+    }
+
+    /// Same as `create_order`, logging the order terms the predicate was built with. The
+    /// response value is the order id, see `order::order_id`.
+    pub async fn create_order_with_options(
+        &self,
+        predicate_root: Address,
+        payment_asset: AssetId,
+        payment_size: u64,
+        base_price: impl Into<Price>,
+        options: &OrderOptions,
+    ) -> Result<FuelCallResponse<Bits256>, fuels::types::errors::Error> {
+        let call = self.create_order_call(
+            predicate_root,
+            payment_asset,
+            payment_size,
+            base_price,
+            options,
+        );
+        let (tx_id, receipts) = submit(&self.proxy.account(), call.build_tx().await?).await?;
+        let mut response = call.get_response(receipts)?;
+        response.tx_id = Some(tx_id);
+        Ok(response)
+    }
+
+    pub async fn build_create_order(
+        &self,
+        predicate_root: Address,
+        payment_asset: AssetId,
+        payment_size: u64,
+        base_price: impl Into<Price>,
+        options: &OrderOptions,
+    ) -> Result<ScriptTransaction, fuels::types::errors::Error> {
+        self.create_order_call(
+            predicate_root,
+            payment_asset,
+            payment_size,
+            base_price,
+            options,
+        )
+        .build_tx()
+        .await
+    }
+
+    /// Puts `base_amount` up for a dutch auction, the response value is the order id.
+    pub async fn create_auction(
+        &self,
+        predicate_root: Address,
+        base_asset: AssetId,
+        base_amount: u64,
+        auction: &DutchAuction,
+        nonce: Bits256,
+    ) -> Result<FuelCallResponse<Bits256>, fuels::types::errors::Error> {
+        let call_params: CallParameters = CallParameters::default()
+            .with_asset_id(base_asset)
+            .with_amount(base_amount);
+        let call = self
+            .proxy
+            .methods()
+            .create_auction(
+                self.market_id,
+                auction.start_price,
+                auction.end_price,
+                auction.start_block,
+                auction.end_block,
+                predicate_root,
+                nonce,
+            )
+            .append_variable_outputs(1)
+            .call_params(call_params)
+            .unwrap()
+            .with_tx_policies(TxPolicies::default().with_gas_price(1));
+        let (tx_id, receipts) = submit(&self.proxy.account(), call.build_tx().await?).await?;
+        let mut response = call.get_response(receipts)?;
+        response.tx_id = Some(tx_id);
+        Ok(response)
+    }
+
+    /// Puts `base_amount` up as an OCO sell order, the response value is the order id.
+    pub async fn create_oco_order(
+        &self,
+        predicate_root: Address,
+        base_asset: AssetId,
+        base_amount: u64,
+        oco: &OcoOrder,
+        nonce: Bits256,
+    ) -> Result<FuelCallResponse<Bits256>, fuels::types::errors::Error> {
+        let call_params: CallParameters = CallParameters::default()
+            .with_asset_id(base_asset)
+            .with_amount(base_amount);
+        let call = self
+            .proxy
+            .methods()
+            .create_oco_order(
+                self.market_id,
+                oco.take_profit_price,
+                oco.stop_price,
+                oco.stop_keeper,
+                predicate_root,
+                nonce,
+            )
+            .append_variable_outputs(1)
+            .call_params(call_params)
+            .unwrap()
+            .with_tx_policies(TxPolicies::default().with_gas_price(1));
+        let (tx_id, receipts) = submit(&self.proxy.account(), call.build_tx().await?).await?;
+        let mut response = call.get_response(receipts)?;
+        response.tx_id = Some(tx_id);
+        Ok(response)
+    }
+
+    /// Puts a ladder up for sale, funded with all of its levels. The response value is the
+    /// order id.
+    pub async fn create_ladder_order(
+        &self,
+        predicate_root: Address,
+        base_asset: AssetId,
+        ladder: &PriceLadder,
+        nonce: Bits256,
+    ) -> Result<FuelCallResponse<Bits256>, fuels::types::errors::Error> {
+        let (prices, sizes, levels) = ladder.to_configurables();
+        let call_params: CallParameters = CallParameters::default()
+            .with_asset_id(base_asset)
+            .with_amount(ladder.total_size());
+        let call = self
+            .proxy
+            .methods()
+            .create_ladder_order(self.market_id, prices, sizes, levels, predicate_root, nonce)
+            .append_variable_outputs(1)
+            .call_params(call_params)
+            .unwrap()
+            .with_tx_policies(TxPolicies::default().with_gas_price(1));
+        let (tx_id, receipts) = submit(&self.proxy.account(), call.build_tx().await?).await?;
+        let mut response = call.get_response(receipts)?;
+        response.tx_id = Some(tx_id);
+        Ok(response)
+    }
+
+    fn create_order_call(
+        &self,
+        predicate_root: Address,
+        payment_asset: AssetId,
+        payment_size: u64,
+        base_price: impl Into<Price>,
+        options: &OrderOptions,
+    ) -> ContractCallHandler<A, Bits256> {
         let call_params: CallParameters = CallParameters::default()
             .with_asset_id(payment_asset)
             .with_amount(payment_size);
+        let price: Price = base_price.into();
+        let (price, price_den) = price.to_configurables();
+        let fee = options.fee.unwrap_or(OrderFee::new(Address::zeroed(), 0));
+        let expiry_script_hash = if options.expiry != Expiry::Never {
+            expiry_script_hash()
+        } else {
+            Bits256([0; 32])
+        };
         self.proxy
             .methods()
-            .create_order(base_price, predicate_root, None)
+            .create_order(
+                self.market_id,
+                price,
+                price_den,
+                predicate_root,
+                options.min_fulfill_amount,
+                options.expiry.to_configurable(),
+                expiry_script_hash,
+                options.nonce,
+                fee.recipient,
+                fee.bps,
+                options.recipient.unwrap_or(Address::zeroed()),
+                options.allowed_taker.unwrap_or(Address::zeroed()),
+                options.all_or_none,
+            )
             .append_variable_outputs(1)
             .call_params(call_params)
             .unwrap()
             .with_tx_policies(TxPolicies::default().with_gas_price(1))
-            .call()
-            .await
     }
 
-    pub fn with_account(&self, account: &WalletUnlocked) -> Self {
-        Self {
+    pub fn with_account<B: SparkAccount>(&self, account: &B) -> Spark<B> {
+        Spark {
             proxy: self.proxy.with_account(account.clone()).unwrap(),
+            market_id: self.market_id,
+            market: self.market.clone(),
         }
     }
 
-    pub async fn new(wallet: &WalletUnlocked, contract_id: &str) -> Self {
-        let proxy = ProxyContract::new(
-            &ContractId::from_str(contract_id).unwrap().into(),
-            wallet.clone(),
-        );
-        Self { proxy }
+    /// A client of the proxy's first market. Errs when `contract_id` isn't a contract id or the
+    /// proxy can't tell its market, e.g. a proxy deployed before `market_info`.
+    pub async fn new(wallet: &A, contract_id: &str) -> Result<Self, fuels::types::errors::Error> {
+        let contract_id = ContractId::from_str(contract_id).map_err(|e| {
+            error!(
+                InvalidData,
+                "invalid proxy contract id `{contract_id}`: {e}"
+            )
+        })?;
+        let proxy = ProxyContract::new(&contract_id.into(), wallet.clone());
+        Self::with_market_info(proxy, 0).await
     }
 
-    pub async fn deploy_proxy(
-        wallet: &WalletUnlocked,
-        base_asset: &Asset,
-        quote_asset: &Asset,
-    ) -> Self {
+    /// The profile's `base_symbol`/`quote_symbol` proxy, from spark.json or the deployment
+    /// manifest, on the market of the profile's assets for these symbols. No built-in profile has
+    /// one.
+    pub async fn from_profile(
+        wallet: &A,
+        profile: &NetworkProfile,
+        base_symbol: &str,
+        quote_symbol: &str,
+    ) -> Result<Self, fuels::types::errors::Error> {
+        let profile_error = |e: NetworkError| error!(InvalidData, "{e}");
+        let contract_id = profile
+            .proxy(base_symbol, quote_symbol)
+            .map_err(profile_error)?
+            .ok_or_else(|| {
+                error!(
+                    InvalidData,
+                    "no {base_symbol}/{quote_symbol} proxy in `{}` profile, deploy one or set it in spark.json",
+                    profile.name
+                )
+            })?;
+        // A proxy can list the pair under any id, the profile knows its assets
+        let asset_id = |symbol: &str| -> Result<AssetId, fuels::types::errors::Error> {
+            profile
+                .asset_id(symbol)
+                .map_err(profile_error)?
+                .ok_or_else(|| {
+                    error!(
+                        InvalidData,
+                        "no {symbol} asset id in `{}` profile, set it in spark.json", profile.name
+                    )
+                })
+        };
+        let base_asset = asset_id(base_symbol)?;
+        let quote_asset = asset_id(quote_symbol)?;
+        let proxy = ProxyContract::new(contract_id, wallet.clone());
+        let spark = Self::with_market_info(proxy, 0).await?;
+        let (market_id, market) = spark
+            .markets()
+            .await?
+            .into_iter()
+            .enumerate()
+            .find(|(_, m)| m.base_asset == base_asset && m.quote_asset == quote_asset)
+            .ok_or_else(|| {
+                error!(
+                    InvalidData,
+                    "no {base_symbol}/{quote_symbol} market in proxy {contract_id}"
+                )
+            })?;
+        Ok(Self {
+            proxy: spark.proxy,
+            market_id: market_id as u64,
+            market,
+        })
+    }
+
+    async fn with_market_info(
+        proxy: ProxyContract<A>,
+        market_id: u64,
+    ) -> Result<Self, fuels::types::errors::Error> {
+        let market = proxy
+            .methods()
+            .market_info(market_id)
+            .simulate()
+            .await
+            .map_err(|e| {
+                error!(
+                    InvalidData,
+                    "can't read market {market_id} of {}: {e}",
+                    proxy.contract_id()
+                )
+            })?
+            .value;
+        Ok(Self {
+            proxy,
+            market_id,
+            market,
+        })
+    }
+
+    pub fn buy_predicate_hash() -> String {
+        file_hash(PathBuf::from(env!("CARGO_MANIFEST_DIR")).join(PREDICATE_BUY_BIN_PATH))
+    }
+
+    pub fn sell_predicate_hash() -> String {
+        file_hash(PathBuf::from(env!("CARGO_MANIFEST_DIR")).join(PREDICATE_SELL_BIN_PATH))
+    }
+
+    /// Deploys a proxy owned by `wallet` with base/quote listed as market 0, `list_market` adds
+    /// more.
+    pub async fn deploy_proxy(wallet: &A, base_asset: &Asset, quote_asset: &Asset) -> Self {
         let mut rng = rand::thread_rng();
         let salt = rng.gen::<[u8; 32]>();
+        Self::deploy_proxy_with_salt(wallet, base_asset, quote_asset, salt).await
+    }
 
+    pub async fn deploy_proxy_with_salt(
+        wallet: &A,
+        base_asset: &Asset,
+        quote_asset: &Asset,
+        salt: [u8; 32],
+    ) -> Self {
+        validate_decimals(
+            PRICE_DECIMALS,
+            base_asset.decimals as u32,
+            quote_asset.decimals as u32,
+        )
+        .unwrap_or_else(|e| panic!("{e}"));
         let proxy_configurables = ProxyContractConfigurables::default()
-            .with_BASE_ASSET(base_asset.asset_id)
-            .with_BASE_ASSET_DECIMALS(base_asset.decimals as u32)
-            .with_QUOTE_ASSET(quote_asset.asset_id)
-            .with_QUOTE_ASSET_DECIMALS(quote_asset.decimals as u32);
+            .with_PRICE_DECIMALS(PRICE_DECIMALS)
+            .with_PREDICATE_VERSION(PREDICATE_VERSION)
+            .with_OWNER(wallet.address().into());
         let config = LoadConfiguration::default().with_configurables(proxy_configurables);
 
         let bin_path = PathBuf::from(env!("CARGO_MANIFEST_DIR")).join(PROXY_BIN_PATH);
@@ -253,6 +1599,259 @@ impl Spark {
 
         let proxy = ProxyContract::new(id, wallet.clone());
 
-        Self { proxy }
+        // The proxy checks every order's root against these
+        for order_type in [OrderType::BUY, OrderType::SELL] {
+            let (bytecode, offsets) = predicate_template(&order_type);
+            let call = proxy
+                .methods()
+                .set_predicate_template(order_type, Bytes(bytecode), offsets)
+                .with_tx_policies(TxPolicies::default().with_gas_price(1));
+            submit(wallet, call.build_tx().await.unwrap())
+                .await
+                .unwrap();
+        }
+        let (bytecode, offsets) = auction_template();
+        let call = proxy
+            .methods()
+            .set_auction_template(Bytes(bytecode), offsets)
+            .with_tx_policies(TxPolicies::default().with_gas_price(1));
+        submit(wallet, call.build_tx().await.unwrap())
+            .await
+            .unwrap();
+        let (bytecode, offsets) = oco_template();
+        let call = proxy
+            .methods()
+            .set_oco_template(Bytes(bytecode), offsets)
+            .with_tx_policies(TxPolicies::default().with_gas_price(1));
+        submit(wallet, call.build_tx().await.unwrap())
+            .await
+            .unwrap();
+        let (bytecode, offsets) = ladder_template();
+        let call = proxy
+            .methods()
+            .set_ladder_template(Bytes(bytecode), offsets)
+            .with_tx_policies(TxPolicies::default().with_gas_price(1));
+        submit(wallet, call.build_tx().await.unwrap())
+            .await
+            .unwrap();
+
+        let market = MarketInfo::new(base_asset, quote_asset);
+        let spark = Self {
+            proxy,
+            market_id: 0,
+            market,
+        };
+        spark.list_market(&spark.market).await.unwrap();
+        spark
+    }
+}
+
+impl Spark<WalletUnlocked> {
+    pub async fn from_named_account(
+        profile: &NetworkProfile,
+        accounts: &Accounts,
+        account: &str,
+        base_symbol: &str,
+        quote_symbol: &str,
+    ) -> Result<Self, AccountError> {
+        let provider = profile.connect().await.map_err(AccountError::Network)?;
+        let wallet = accounts.wallet(account, &provider)?;
+        Self::from_profile(&wallet, profile, base_symbol, quote_symbol)
+            .await
+            .map_err(AccountError::Proxy)
+    }
+}
+
+// What `set_predicate_template` takes for the buy or sell predicate: its bytecode with the default
+// configurables and where it keeps each of them, the offsets abigen writes them at
+pub fn predicate_template(order_type: &OrderType) -> (Vec<u8>, PredicateOffsets) {
+    let bin_path = match order_type {
+        OrderType::BUY => PREDICATE_BUY_BIN_PATH,
+        OrderType::SELL => PREDICATE_SELL_BIN_PATH,
+    };
+    let bytecode = std::fs::read(bin_path).unwrap();
+    (bytecode, order_predicate_offsets(order_type))
+}
+
+// The options a buy or sell predicate was built with, read back from the configurables in its
+// bytecode. Fills need them to lay the tx out the way the predicate checks it.
+pub fn predicate_options(predicate: &Predicate, order_type: &OrderType) -> OrderOptions {
+    let offsets = order_predicate_offsets(order_type);
+    let code = predicate.code();
+    let word = |offset: u64| u64::from_be_bytes(code[offset as usize..][..8].try_into().unwrap());
+    let b256 = |offset: u64| -> [u8; 32] { code[offset as usize..][..32].try_into().unwrap() };
+    let address =
+        |offset: u64| Some(Address::from(b256(offset))).filter(|a| *a != Address::zeroed());
+
+    let fee_bps = word(offsets.fee_bps);
+    let fee = match (address(offsets.fee_recipient), fee_bps) {
+        (None, 0) => None,
+        (recipient, bps) => Some(OrderFee::new(recipient.unwrap_or(Address::zeroed()), bps)),
+    };
+    OrderOptions {
+        min_fulfill_amount: word(offsets.min_fulfill_amount),
+        expiry: Expiry::from_configurable(word(offsets.expiry)),
+        nonce: Bits256(b256(offsets.nonce)),
+        fee,
+        recipient: address(offsets.recipient),
+        allowed_taker: address(offsets.allowed_taker),
+        all_or_none: word(offsets.all_or_none) != 0,
     }
 }
+
+fn order_predicate_offsets(order_type: &OrderType) -> PredicateOffsets {
+    match order_type {
+        OrderType::BUY => predicate_offsets(PREDICATE_BUY_ABI_PATH, "MIN_FULFILL_QUOTE_AMOUNT"),
+        OrderType::SELL => predicate_offsets(PREDICATE_SELL_ABI_PATH, "MIN_FULFILL_BASE_AMOUNT"),
+    }
+}
+
+fn predicate_offsets(abi_path: &str, min_fulfill: &str) -> PredicateOffsets {
+    let offset = configurable_offsets(abi_path);
+    PredicateOffsets {
+        base_asset: offset("BASE_ASSET"),
+        quote_asset: offset("QUOTE_ASSET"),
+        maker: offset("MAKER"),
+        price: offset("PRICE"),
+        price_den: offset("PRICE_DEN"),
+        base_decimals: offset("BASE_DECIMALS"),
+        quote_decimals: offset("QUOTE_DECIMALS"),
+        price_decimals: offset("PRICE_DECIMALS"),
+        min_fulfill_amount: offset(min_fulfill),
+        expiry: offset("EXPIRY"),
+        expiry_script_hash: offset("EXPIRY_SCRIPT_HASH"),
+        nonce: offset("NONCE"),
+        fee_recipient: offset("FEE_RECIPIENT"),
+        fee_bps: offset("FEE_BPS"),
+        recipient: offset("RECIPIENT"),
+        allowed_taker: offset("ALLOWED_TAKER"),
+        all_or_none: offset("ALL_OR_NONE"),
+    }
+}
+
+// What `set_auction_template` takes, like `predicate_template`
+pub fn auction_template() -> (Vec<u8>, AuctionOffsets) {
+    let bytecode = std::fs::read(PREDICATE_DUTCH_AUCTION_BIN_PATH).unwrap();
+    let offset = configurable_offsets(PREDICATE_DUTCH_AUCTION_ABI_PATH);
+    let offsets = AuctionOffsets {
+        base_asset: offset("BASE_ASSET"),
+        quote_asset: offset("QUOTE_ASSET"),
+        maker: offset("MAKER"),
+        start_price: offset("START_PRICE"),
+        end_price: offset("END_PRICE"),
+        start_block: offset("START_BLOCK"),
+        end_block: offset("END_BLOCK"),
+        base_decimals: offset("BASE_DECIMALS"),
+        quote_decimals: offset("QUOTE_DECIMALS"),
+        price_decimals: offset("PRICE_DECIMALS"),
+        nonce: offset("NONCE"),
+    };
+    (bytecode, offsets)
+}
+
+// What `set_oco_template` takes, like `predicate_template`
+pub fn oco_template() -> (Vec<u8>, OcoOffsets) {
+    let bytecode = std::fs::read(PREDICATE_OCO_BIN_PATH).unwrap();
+    let offset = configurable_offsets(PREDICATE_OCO_ABI_PATH);
+    let offsets = OcoOffsets {
+        base_asset: offset("BASE_ASSET"),
+        quote_asset: offset("QUOTE_ASSET"),
+        maker: offset("MAKER"),
+        take_profit_price: offset("TAKE_PROFIT_PRICE"),
+        stop_price: offset("STOP_PRICE"),
+        stop_keeper: offset("STOP_KEEPER"),
+        base_decimals: offset("BASE_DECIMALS"),
+        quote_decimals: offset("QUOTE_DECIMALS"),
+        price_decimals: offset("PRICE_DECIMALS"),
+        nonce: offset("NONCE"),
+    };
+    (bytecode, offsets)
+}
+
+// What `set_ladder_template` takes, like `predicate_template`
+pub fn ladder_template() -> (Vec<u8>, LadderOffsets) {
+    let bytecode = std::fs::read(PREDICATE_LADDER_BIN_PATH).unwrap();
+    let offset = configurable_offsets(PREDICATE_LADDER_ABI_PATH);
+    let offsets = LadderOffsets {
+        base_asset: offset("BASE_ASSET"),
+        quote_asset: offset("QUOTE_ASSET"),
+        maker: offset("MAKER"),
+        prices: offset("PRICES"),
+        sizes: offset("SIZES"),
+        levels: offset("LEVELS"),
+        base_decimals: offset("BASE_DECIMALS"),
+        quote_decimals: offset("QUOTE_DECIMALS"),
+        price_decimals: offset("PRICE_DECIMALS"),
+        nonce: offset("NONCE"),
+    };
+    (bytecode, offsets)
+}
+
+// Looks configurables up by name in a predicate ABI, giving the offset abigen writes them at
+fn configurable_offsets(abi_path: &str) -> impl Fn(&str) -> u64 + '_ {
+    let abi: serde_json::Value =
+        serde_json::from_str(&std::fs::read_to_string(abi_path).unwrap()).unwrap();
+    move |name: &str| {
+        abi["configurables"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .find(|c| c["name"] == name)
+            .and_then(|c| c["offset"].as_u64())
+            .unwrap_or_else(|| panic!("No {name} configurable in {abi_path}"))
+    }
+}
+
+pub fn expiry_script_bytecode() -> Vec<u8> {
+    std::fs::read(EXPIRY_SCRIPT_BIN_PATH).unwrap()
+}
+
+// What the predicates compare `tx_script_bytecode_hash()` against
+pub fn expiry_script_hash() -> Bits256 {
+    Bits256(Sha256::digest(expiry_script_bytecode()).into())
+}
+
+async fn predicate_coin_input(
+    predicate: &Predicate,
+    asset: AssetId,
+    utxo_id: UtxoId,
+) -> Result<Input, fuels::prelude::Error> {
+    let coin = predicate
+        .try_provider()?
+        .get_coins(predicate.address(), asset)
+        .await?
+        .into_iter()
+        .find(|coin| coin.utxo_id == utxo_id)
+        .ok_or_else(|| error!(InvalidData, "coin {utxo_id:?} of the predicate is spent"))?;
+    Ok(Input::resource_predicate(
+        CoinType::Coin(coin),
+        predicate.code().to_vec(),
+        predicate.data().clone(),
+    ))
+}
+
+async fn send_script_call<W: SparkAccount>(
+    wallet: &W,
+    script_call: ScriptCallHandler<W, ()>,
+) -> Result<FuelCallResponse<()>, fuels::prelude::Error> {
+    let (tx_id, receipts) = submit(wallet, script_call.build_tx().await?).await?;
+    let mut response = script_call.get_response(receipts)?;
+    response.tx_id = Some(tx_id);
+    Ok(response)
+}
+
+// Sends on `A`'s provider, lets `A` sign (see `SparkAccount::finalize_tx`) and waits for
+// the tx to be committed
+pub async fn submit<A: SparkAccount>(
+    account: &A,
+    tx: ScriptTransaction,
+) -> Result<(Bytes32, Vec<Receipt>), fuels::prelude::Error> {
+    let provider = account.try_provider()?;
+    let tx = account.finalize_tx(tx).await?;
+    let tx_id = tx.id(provider.chain_id());
+    let receipts = provider
+        .send_transaction_and_await_commit(tx)
+        .await?
+        .take_receipts_checked(None)?;
+    Ok((tx_id, receipts))
+}
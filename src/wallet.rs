@@ -0,0 +1,235 @@
+use std::collections::BTreeMap;
+use std::env;
+use std::fmt;
+use std::path::{Path, PathBuf};
+
+use fuels::accounts::wallet::WalletUnlocked;
+use fuels::crypto::SecretKey;
+use fuels::prelude::Provider;
+use serde::{Deserialize, Serialize};
+
+use crate::network::NetworkError;
+use crate::signer::LocalSigner;
+
+pub const ACCOUNTS_ENV: &str = "SPARK_ACCOUNTS";
+pub const DEFAULT_ACCOUNTS_FILE: &str = "accounts.json";
+pub const KEYSTORE_PASSWORD_ENV: &str = "SPARK_KEYSTORE_PASSWORD";
+pub const FUEL_DERIVATION_PATH_PREFIX: &str = "m/44'/1179993420'";
+
+#[derive(Debug)]
+pub enum AccountError {
+    UnknownAccount(String),
+    MissingEnv(String),
+    InvalidPrivateKey(String),
+    KeyDerivation(String),
+    Io(std::io::Error),
+    Json(serde_json::Error),
+    Wallet(fuels::types::errors::Error),
+    Proxy(fuels::types::errors::Error),
+    Network(NetworkError),
+}
+
+impl fmt::Display for AccountError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            AccountError::UnknownAccount(name) => write!(f, "unknown account `{name}`"),
+            AccountError::MissingEnv(var) => write!(f, "env variable `{var}` is not set"),
+            AccountError::InvalidPrivateKey(var) => {
+                write!(f, "env variable `{var}` is not a private key")
+            }
+            AccountError::KeyDerivation(e) => write!(f, "can't derive key: {e}"),
+            AccountError::Io(e) => write!(f, "accounts io error: {e}"),
+            AccountError::Json(e) => write!(f, "accounts json error: {e}"),
+            AccountError::Wallet(e) => write!(f, "can't unlock wallet: {e}"),
+            AccountError::Proxy(e) => write!(f, "can't read the proxy: {e}"),
+            AccountError::Network(e) => write!(f, "{e}"),
+        }
+    }
+}
+
+impl std::error::Error for AccountError {}
+
+impl From<std::io::Error> for AccountError {
+    fn from(e: std::io::Error) -> Self {
+        AccountError::Io(e)
+    }
+}
+
+impl From<serde_json::Error> for AccountError {
+    fn from(e: serde_json::Error) -> Self {
+        AccountError::Json(e)
+    }
+}
+
+impl From<fuels::types::errors::Error> for AccountError {
+    fn from(e: fuels::types::errors::Error) -> Self {
+        AccountError::Wallet(e)
+    }
+}
+
+// Secrets never live in the accounts file itself, only env variable names and keystore paths
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq, Eq)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum AccountSource {
+    // Encrypted JSON keystore, unlocked with `password_env` or SPARK_KEYSTORE_PASSWORD
+    Keystore {
+        path: PathBuf,
+        #[serde(default)]
+        password_env: Option<String>,
+    },
+    // BIP-39 phrase from `phrase_env`, either an explicit derivation path or the
+    // `m/44'/1179993420'/{index}'/0/0` subaccount
+    Mnemonic {
+        phrase_env: String,
+        #[serde(default)]
+        derivation_path: Option<String>,
+        #[serde(default)]
+        index: u32,
+    },
+    // Raw hex private key, what ADMIN/ALICE/BOB used to be
+    PrivateKey {
+        env: String,
+    },
+}
+
+impl AccountSource {
+    pub fn derivation_path(&self) -> Option<String> {
+        match self {
+            AccountSource::Mnemonic {
+                derivation_path,
+                index,
+                ..
+            } => Some(
+                derivation_path
+                    .clone()
+                    .unwrap_or_else(|| subaccount_path(*index)),
+            ),
+            _ => None,
+        }
+    }
+
+    pub fn unlock(&self, provider: Option<Provider>) -> Result<WalletUnlocked, AccountError> {
+        Ok(WalletUnlocked::new_from_private_key(
+            self.secret_key()?,
+            provider,
+        ))
+    }
+
+    pub fn secret_key(&self) -> Result<SecretKey, AccountError> {
+        match self {
+            AccountSource::Keystore { path, password_env } => {
+                let password_env = password_env.as_deref().unwrap_or(KEYSTORE_PASSWORD_ENV);
+                let password = read_env(password_env)?;
+                let key = eth_keystore::decrypt_key(path, password)
+                    .map_err(|e| AccountError::KeyDerivation(e.to_string()))?;
+                SecretKey::try_from(key.as_slice())
+                    .map_err(|e| AccountError::KeyDerivation(e.to_string()))
+            }
+            AccountSource::Mnemonic { phrase_env, .. } => {
+                let phrase = read_env(phrase_env)?;
+                let path = self.derivation_path().unwrap();
+                SecretKey::new_from_mnemonic_phrase_with_path(&phrase, &path)
+                    .map_err(|e| AccountError::KeyDerivation(e.to_string()))
+            }
+            AccountSource::PrivateKey { env } => read_env(env)?
+                .parse()
+                .map_err(|_| AccountError::InvalidPrivateKey(env.clone())),
+        }
+    }
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq, Eq)]
+pub struct Accounts {
+    // account name => where its key comes from, e.g. "maker-grid-1" => mnemonic index 3
+    #[serde(flatten)]
+    pub accounts: BTreeMap<String, AccountSource>,
+}
+
+impl Default for Accounts {
+    // The ADMIN/ALICE/BOB env keys the scripts have always used
+    fn default() -> Self {
+        let private_key = |env: &str| AccountSource::PrivateKey {
+            env: env.to_string(),
+        };
+        Self {
+            accounts: BTreeMap::from([
+                ("admin".to_string(), private_key("ADMIN")),
+                ("maker".to_string(), private_key("ALICE")),
+                ("taker".to_string(), private_key("BOB")),
+            ]),
+        }
+    }
+}
+
+impl Accounts {
+    pub fn path_from_env() -> PathBuf {
+        env::var(ACCOUNTS_ENV)
+            .map(PathBuf::from)
+            .unwrap_or(PathBuf::from(DEFAULT_ACCOUNTS_FILE))
+    }
+
+    /// Reads `SPARK_ACCOUNTS` or `./accounts.json`, falling back to the ADMIN/ALICE/BOB keys.
+    pub fn from_env() -> Result<Self, AccountError> {
+        let path = Self::path_from_env();
+        if path.exists() {
+            Self::load(path)
+        } else {
+            Ok(Self::default())
+        }
+    }
+
+    pub fn load(path: impl AsRef<Path>) -> Result<Self, AccountError> {
+        let json = std::fs::read_to_string(path)?;
+        Ok(serde_json::from_str(&json)?)
+    }
+
+    pub fn save(&self, path: impl AsRef<Path>) -> Result<(), AccountError> {
+        let json = serde_json::to_string_pretty(self)?;
+        std::fs::write(path, json)?;
+        Ok(())
+    }
+
+    pub fn insert(&mut self, name: &str, source: AccountSource) {
+        self.accounts.insert(name.to_string(), source);
+    }
+
+    pub fn names(&self) -> impl Iterator<Item = &String> {
+        self.accounts.keys()
+    }
+
+    pub fn source(&self, name: &str) -> Result<&AccountSource, AccountError> {
+        self.accounts
+            .get(name)
+            .ok_or_else(|| AccountError::UnknownAccount(name.to_string()))
+    }
+
+    pub fn wallet(&self, name: &str, provider: &Provider) -> Result<WalletUnlocked, AccountError> {
+        self.source(name)?.unlock(Some(provider.clone()))
+    }
+
+    /// Signer holding the account's key, for signing offline artifacts without a provider.
+    pub fn signer(&self, name: &str) -> Result<LocalSigner, AccountError> {
+        Ok(LocalSigner::new(self.source(name)?.secret_key()?))
+    }
+
+    /// Wallet of the account playing `role` in a script: `SPARK_<ROLE>_ACCOUNT` or the role itself.
+    pub fn wallet_for_role(
+        &self,
+        role: &str,
+        provider: &Provider,
+    ) -> Result<WalletUnlocked, AccountError> {
+        self.wallet(&role_account(role), provider)
+    }
+}
+
+pub fn role_account(role: &str) -> String {
+    env::var(format!("SPARK_{}_ACCOUNT", role.to_uppercase())).unwrap_or(role.to_string())
+}
+
+pub fn subaccount_path(index: u32) -> String {
+    format!("{FUEL_DERIVATION_PATH_PREFIX}/{index}'/0/0")
+}
+
+fn read_env(var: &str) -> Result<String, AccountError> {
+    env::var(var).map_err(|_| AccountError::MissingEnv(var.to_string()))
+}
@@ -1,7 +1,7 @@
 use fuels::test_helpers::{launch_custom_provider_and_get_wallets, WalletsConfig};
 use fuels::{prelude::ViewOnlyAccount, types::Address};
-use spark_sdk::spark_utils::Spark;
 use spark_sdk::print_title;
+use spark_sdk::spark_utils::{CancelOrderEvent, Spark};
 use src20_sdk::token_utils::{deploy_token_contract, Asset};
 
 // example of inputs and outputs
@@ -13,6 +13,7 @@ inputs
 outputs
    Coin { to: Alice, amount: 0, asset_id: USDC }
    Change { to: Alice, amount: 0, asset_id: USDC }
+   Contract { input_index: .. } (the proxy call logging the cancel)
  */
 #[tokio::test]
 async fn cancel_order_test() {
@@ -44,7 +45,7 @@ async fn cancel_order_test() {
     let initial_alice_usdc_balance = alice.get_asset_balance(&usdc.asset_id).await.unwrap();
 
     let spark = Spark::deploy_proxy(admin, &btc, &usdc).await;
-    let buy_predicate = spark.get_buy_predicate(alice, &btc, &usdc, price, 1);
+    let buy_predicate = spark.get_buy_predicate(alice, price, 1);
     assert!(alice.get_asset_balance(&usdc.asset_id).await.unwrap() == quote_amount);
 
     spark
@@ -54,20 +55,51 @@ async fn cancel_order_test() {
             usdc.asset_id,
             quote_amount,
             price,
+            1,
         )
         .await
         .unwrap();
 
-    spark
+    // Only the maker can take the coins back
+    let bob = &wallets[2];
+    let res = spark
+        .cancel_order(bob, &buy_predicate, usdc.asset_id, quote_amount)
+        .await;
+    assert!(res.is_err());
+
+    let response = spark
         .cancel_order(&alice, &buy_predicate, usdc.asset_id, quote_amount)
         .await
         .unwrap();
+    let events = response
+        .decode_logs_with_type::<CancelOrderEvent>()
+        .unwrap();
+    assert_eq!(events.len(), 1);
+    assert_eq!(events[0].predicate_root, buy_predicate.address().into());
+    assert_eq!(events[0].maker, alice_address);
+    assert_eq!(events[0].base_amount, 0);
+    assert_eq!(events[0].quote_amount, quote_amount);
+
+    // Indexers get the same events from the tx id alone
+    let indexed = spark
+        .reader()
+        .cancel_events(&response.tx_id.unwrap())
+        .await
+        .unwrap();
+    assert_eq!(indexed, events);
+
     let predicate_balance = buy_predicate
         .get_asset_balance(&usdc.asset_id)
         .await
         .unwrap();
     assert_eq!(predicate_balance, 0);
 
+    // Nothing left to cancel is an error, not a panic
+    let res = spark
+        .cancel_order(&alice, &buy_predicate, usdc.asset_id, quote_amount)
+        .await;
+    assert!(res.is_err());
+
     // Wallet balance is the same as before it sent the coins to the predicate
     let wallet_balance = alice.get_asset_balance(&usdc.asset_id).await.unwrap();
     assert_eq!(wallet_balance, initial_alice_usdc_balance);
@@ -0,0 +1,513 @@
+use async_trait::async_trait;
+use fuels::accounts::wallet::WalletUnlocked;
+use fuels::accounts::{Account, ViewOnlyAccount};
+use fuels::crypto::SecretKey;
+use fuels::prelude::{Bech32Address, Provider, TxPolicies, BASE_ASSET_ID};
+use fuels::test_helpers::{launch_custom_provider_and_get_wallets, WalletsConfig};
+use fuels::tx::field::Outputs;
+use fuels::tx::{Output, Script};
+use fuels::types::errors::Result;
+use fuels::types::transaction::ScriptTransaction;
+use fuels::types::transaction_builders::TransactionBuilder;
+use fuels::types::Address;
+use spark_sdk::auction::DutchAuction;
+use spark_sdk::ladder::{LadderLevel, PriceLadder};
+use spark_sdk::order::{random_nonce, Expiry};
+use spark_sdk::print_title;
+use spark_sdk::rfq::RfqQuote;
+use spark_sdk::signer::{sign_transaction, LocalSigner, SignerAccount, SparkAccount};
+use spark_sdk::spark_utils::Spark;
+use src20_sdk::token_utils::{deploy_token_contract, Asset};
+
+// A taker that builds honest fills and then, before signing, points every change output at
+// its own address, the predicate's change of the asset it offers included
+#[derive(Debug, Clone)]
+struct ChangeThief(SignerAccount<LocalSigner>);
+
+#[async_trait]
+impl ViewOnlyAccount for ChangeThief {
+    fn address(&self) -> &Bech32Address {
+        self.0.address()
+    }
+
+    fn try_provider(&self) -> Result<&Provider> {
+        self.0.try_provider()
+    }
+}
+
+#[async_trait]
+impl Account for ChangeThief {
+    fn add_witnessses<Tb: TransactionBuilder>(&self, tb: &mut Tb) {
+        self.0.add_witnessses(tb)
+    }
+}
+
+#[async_trait]
+impl SparkAccount for ChangeThief {
+    async fn finalize_tx(&self, tx: ScriptTransaction) -> Result<ScriptTransaction> {
+        let mut script: Script = tx.into();
+        let thief = Address::from(self.address());
+        for output in script.outputs_mut() {
+            if let Output::Change { to, .. } = output {
+                *to = thief;
+            }
+        }
+        let chain_id = self.try_provider()?.chain_id();
+        sign_transaction(self.0.signer(), script.into(), chain_id).await
+    }
+}
+
+struct Setup {
+    admin: WalletUnlocked,
+    alice: WalletUnlocked,
+    thief: ChangeThief,
+    btc: Asset,
+    usdc: Asset,
+    provider: Provider,
+}
+
+async fn setup() -> Setup {
+    let config = WalletsConfig::new(Some(5), Some(1), Some(1_000_000_000));
+    let wallets = launch_custom_provider_and_get_wallets(config, None, None)
+        .await
+        .unwrap();
+    let admin = wallets[0].clone();
+    let alice = wallets[1].clone();
+    let provider = admin.try_provider().unwrap().clone();
+
+    let thief_key = SecretKey::random(&mut rand::thread_rng());
+    let thief = ChangeThief(SignerAccount::new(
+        LocalSigner::new(thief_key),
+        provider.clone(),
+    ));
+    admin
+        .transfer(
+            thief.address(),
+            100_000_000,
+            BASE_ASSET_ID,
+            TxPolicies::default().with_gas_price(1),
+        )
+        .await
+        .unwrap();
+
+    let token_contract = deploy_token_contract(&admin).await;
+    let usdc = Asset::new(admin.clone(), token_contract.contract_id().into(), "USDC");
+    let btc = Asset::new(admin.clone(), token_contract.contract_id().into(), "BTC");
+    Setup {
+        admin,
+        alice,
+        thief,
+        btc,
+        usdc,
+        provider,
+    }
+}
+
+// 0.0001 BTC bought at the auction price, with the rest of the BTC coin taken as change
+#[tokio::test]
+async fn dutch_auction_change_theft_test() {
+    print_title("Dutch Auction Change Theft Test");
+    let Setup {
+        admin,
+        alice,
+        thief,
+        btc,
+        usdc,
+        provider,
+    } = setup().await;
+    let base_amount = btc.parse_units(1_f64) as u64; // 1 BTC
+    let fill_amount = base_amount / 10_000;
+    let exp = 9 + btc.decimals - usdc.decimals;
+    let price = (usdc.parse_units(40_000_f64) as u128 * 10u128.pow(exp as u32)
+        / base_amount as u128) as u64;
+
+    btc.mint(Address::from(alice.address()), base_amount)
+        .await
+        .unwrap();
+    usdc.mint(
+        Address::from(thief.address()),
+        usdc.parse_units(10_f64) as u64,
+    )
+    .await
+    .unwrap();
+
+    let spark = Spark::deploy_proxy(&admin, &btc, &usdc).await;
+    let start_block = provider.latest_block_height().await.unwrap();
+    let auction = DutchAuction::new(price, price / 2, start_block, start_block + 100);
+    let nonce = random_nonce();
+    let predicate = spark.get_dutch_auction_predicate(&alice, &auction, nonce);
+    spark
+        .with_account(&alice)
+        .create_auction(
+            predicate.address().into(),
+            btc.asset_id,
+            base_amount,
+            &auction,
+            nonce,
+        )
+        .await
+        .unwrap();
+
+    let height = provider.latest_block_height().await.unwrap();
+    let quote = auction.quote_amount_at(
+        fill_amount,
+        height,
+        btc.decimals as u32,
+        usdc.decimals as u32,
+    );
+    let res = spark
+        .fulfill_auction(
+            &thief,
+            &predicate,
+            alice.address(),
+            btc.asset_id,
+            fill_amount,
+            usdc.asset_id,
+            quote,
+            height,
+        )
+        .await;
+    assert!(res.is_err());
+    let balance = predicate.get_asset_balance(&btc.asset_id).await.unwrap();
+    assert_eq!(balance, base_amount);
+
+    // The same fill with the change left alone goes through
+    spark
+        .fulfill_auction(
+            &thief.0,
+            &predicate,
+            alice.address(),
+            btc.asset_id,
+            fill_amount,
+            usdc.asset_id,
+            quote,
+            height,
+        )
+        .await
+        .unwrap();
+    let balance = predicate.get_asset_balance(&btc.asset_id).await.unwrap();
+    assert_eq!(balance, base_amount - fill_amount);
+}
+
+// A fill within the quote's max size, with the rest of the quoted coin taken as change
+#[tokio::test]
+async fn rfq_change_theft_test() {
+    print_title("RFQ Change Theft Test");
+    let Setup {
+        admin,
+        thief,
+        btc,
+        usdc,
+        provider,
+        ..
+    } = setup().await;
+    let base_amount = btc.parse_units(1_f64) as u64; // 1 BTC
+    let fill_amount = base_amount / 10_000;
+    let exp = 9 + btc.decimals - usdc.decimals;
+    let quote_amount = usdc.parse_units(4_f64) as u64; // 4 USDC for 0.0001 BTC
+    let price = (quote_amount as u128 * 10u128.pow(exp as u32) / fill_amount as u128) as u64;
+
+    // The maker signs quotes with a key of its own
+    let alice_key = SecretKey::random(&mut rand::thread_rng());
+    let alice = WalletUnlocked::new_from_private_key(alice_key, Some(provider.clone()));
+    admin
+        .transfer(
+            alice.address(),
+            100_000_000,
+            BASE_ASSET_ID,
+            TxPolicies::default().with_gas_price(1),
+        )
+        .await
+        .unwrap();
+    btc.mint(Address::from(alice.address()), base_amount)
+        .await
+        .unwrap();
+    usdc.mint(Address::from(thief.address()), 2 * quote_amount)
+        .await
+        .unwrap();
+
+    let spark = Spark::deploy_proxy(&admin, &btc, &usdc).await;
+    let ask = spark.get_rfq_predicate(&alice, false);
+    alice
+        .transfer(
+            ask.address(),
+            base_amount,
+            btc.asset_id,
+            TxPolicies::default().with_gas_price(1),
+        )
+        .await
+        .unwrap();
+    let coin = spark
+        .reader()
+        .rfq_coin(ask.address(), btc.asset_id)
+        .await
+        .unwrap()
+        .unwrap();
+    let expiry = Expiry::BlockHeight(provider.latest_block_height().await.unwrap() + 50);
+    let quote = RfqQuote::new(coin, price, fill_amount, expiry, 1)
+        .sign(
+            &LocalSigner::new(alice_key),
+            &btc.asset_id,
+            &usdc.asset_id,
+            false,
+        )
+        .await
+        .unwrap();
+
+    let res = spark
+        .fulfill_rfq(
+            &thief,
+            &ask,
+            alice.address(),
+            &quote,
+            btc.asset_id,
+            fill_amount,
+            usdc.asset_id,
+            quote_amount,
+        )
+        .await;
+    assert!(res.is_err());
+    let balance = ask.get_asset_balance(&btc.asset_id).await.unwrap();
+    assert_eq!(balance, base_amount);
+
+    // The same fill with the change left alone goes through
+    spark
+        .fulfill_rfq(
+            &thief.0,
+            &ask,
+            alice.address(),
+            &quote,
+            btc.asset_id,
+            fill_amount,
+            usdc.asset_id,
+            quote_amount,
+        )
+        .await
+        .unwrap();
+    let balance = ask.get_asset_balance(&btc.asset_id).await.unwrap();
+    assert_eq!(balance, base_amount - fill_amount);
+}
+
+// 0.0001 BTC off the first level of a ladder, with the rest of the ladder's coin taken as change
+#[tokio::test]
+async fn ladder_change_theft_test() {
+    print_title("Ladder Change Theft Test");
+    let Setup {
+        admin,
+        alice,
+        thief,
+        btc,
+        usdc,
+        ..
+    } = setup().await;
+    let base_amount = btc.parse_units(1_f64) as u64; // 1 BTC
+    let fill_amount = base_amount / 10_000;
+    let exp = 9 + btc.decimals - usdc.decimals;
+    let price_of = |usd: f64| {
+        (usdc.parse_units(usd) as u128 * 10u128.pow(exp as u32) / base_amount as u128) as u64
+    };
+
+    btc.mint(Address::from(alice.address()), base_amount)
+        .await
+        .unwrap();
+    usdc.mint(
+        Address::from(thief.address()),
+        usdc.parse_units(10_f64) as u64,
+    )
+    .await
+    .unwrap();
+
+    let spark = Spark::deploy_proxy(&admin, &btc, &usdc).await;
+    let ladder = PriceLadder::new(vec![
+        LadderLevel::new(price_of(40_000_f64), base_amount / 2),
+        LadderLevel::new(price_of(41_000_f64), base_amount / 2),
+    ]);
+    let nonce = random_nonce();
+    let predicate = spark.get_ladder_predicate(&alice, &ladder, nonce);
+    spark
+        .with_account(&alice)
+        .create_ladder_order(predicate.address().into(), btc.asset_id, &ladder, nonce)
+        .await
+        .unwrap();
+
+    let quote = spark
+        .reader()
+        .ladder_fill_quote(predicate.address(), &ladder, spark.market(), fill_amount)
+        .await
+        .unwrap()
+        .unwrap();
+    let res = spark
+        .fulfill_ladder(
+            &thief,
+            &predicate,
+            alice.address(),
+            btc.asset_id,
+            fill_amount,
+            usdc.asset_id,
+            quote,
+        )
+        .await;
+    assert!(res.is_err());
+    let balance = predicate.get_asset_balance(&btc.asset_id).await.unwrap();
+    assert_eq!(balance, base_amount);
+
+    // The same fill with the change left alone goes through
+    spark
+        .fulfill_ladder(
+            &thief.0,
+            &predicate,
+            alice.address(),
+            btc.asset_id,
+            fill_amount,
+            usdc.asset_id,
+            quote,
+        )
+        .await
+        .unwrap();
+    let balance = predicate.get_asset_balance(&btc.asset_id).await.unwrap();
+    assert_eq!(balance, base_amount - fill_amount);
+}
+
+// 0.0001 BTC bought off a sell order, with the rest of the order's BTC taken as change
+#[tokio::test]
+async fn sell_order_change_theft_test() {
+    print_title("Sell Order Change Theft Test");
+    let Setup {
+        admin,
+        alice,
+        thief,
+        btc,
+        usdc,
+        ..
+    } = setup().await;
+    let base_amount = btc.parse_units(1_f64) as u64; // 1 BTC
+    let fill_amount = base_amount / 10_000;
+    let exp = 9 + btc.decimals - usdc.decimals;
+    let price = (usdc.parse_units(40_000_f64) as u128 * 10u128.pow(exp as u32)
+        / base_amount as u128) as u64;
+    let quote = usdc.parse_units(4_f64) as u64;
+
+    btc.mint(Address::from(alice.address()), base_amount)
+        .await
+        .unwrap();
+    usdc.mint(Address::from(thief.address()), 2 * quote)
+        .await
+        .unwrap();
+
+    let spark = Spark::deploy_proxy(&admin, &btc, &usdc).await;
+    let predicate = spark.get_sell_predicate(&alice, price, 1);
+    spark
+        .with_account(&alice)
+        .create_order(
+            predicate.address().into(),
+            btc.asset_id,
+            base_amount,
+            price,
+            1,
+        )
+        .await
+        .unwrap();
+
+    let res = spark
+        .fulfill_order(
+            &thief,
+            &predicate,
+            alice.address(),
+            btc.asset_id,
+            fill_amount,
+            usdc.asset_id,
+            quote,
+        )
+        .await;
+    assert!(res.is_err());
+    let balance = predicate.get_asset_balance(&btc.asset_id).await.unwrap();
+    assert_eq!(balance, base_amount);
+
+    // The same fill with the change left alone goes through
+    spark
+        .fulfill_order(
+            &thief.0,
+            &predicate,
+            alice.address(),
+            btc.asset_id,
+            fill_amount,
+            usdc.asset_id,
+            quote,
+        )
+        .await
+        .unwrap();
+    let balance = predicate.get_asset_balance(&btc.asset_id).await.unwrap();
+    assert_eq!(balance, base_amount - fill_amount);
+}
+
+// 4 USDC sold to a buy order, with the rest of the order's USDC taken as change
+#[tokio::test]
+async fn buy_order_change_theft_test() {
+    print_title("Buy Order Change Theft Test");
+    let Setup {
+        admin,
+        alice,
+        thief,
+        btc,
+        usdc,
+        ..
+    } = setup().await;
+    let quote_amount = usdc.parse_units(40_000_f64) as u64; // 40k USDC
+    let base_amount = btc.parse_units(1_f64) as u64; // 1 BTC
+    let exp = 9 + btc.decimals - usdc.decimals;
+    let price = (quote_amount as u128 * 10u128.pow(exp as u32) / base_amount as u128) as u64;
+    let fill_amount = quote_amount / 10_000;
+    let base = base_amount / 10_000;
+
+    usdc.mint(Address::from(alice.address()), quote_amount)
+        .await
+        .unwrap();
+    btc.mint(Address::from(thief.address()), 2 * base)
+        .await
+        .unwrap();
+
+    let spark = Spark::deploy_proxy(&admin, &btc, &usdc).await;
+    let predicate = spark.get_buy_predicate(&alice, price, 1);
+    spark
+        .with_account(&alice)
+        .create_order(
+            predicate.address().into(),
+            usdc.asset_id,
+            quote_amount,
+            price,
+            1,
+        )
+        .await
+        .unwrap();
+
+    let res = spark
+        .fulfill_order(
+            &thief,
+            &predicate,
+            alice.address(),
+            usdc.asset_id,
+            fill_amount,
+            btc.asset_id,
+            base,
+        )
+        .await;
+    assert!(res.is_err());
+    let balance = predicate.get_asset_balance(&usdc.asset_id).await.unwrap();
+    assert_eq!(balance, quote_amount);
+
+    // The same fill with the change left alone goes through
+    spark
+        .fulfill_order(
+            &thief.0,
+            &predicate,
+            alice.address(),
+            usdc.asset_id,
+            fill_amount,
+            btc.asset_id,
+            base,
+        )
+        .await
+        .unwrap();
+    let balance = predicate.get_asset_balance(&usdc.asset_id).await.unwrap();
+    assert_eq!(balance, quote_amount - fill_amount);
+}
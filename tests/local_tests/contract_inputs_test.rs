@@ -0,0 +1,104 @@
+use fuels::test_helpers::{launch_custom_provider_and_get_wallets, WalletsConfig};
+use fuels::{prelude::ViewOnlyAccount, types::Address};
+use spark_sdk::order::OrderOptions;
+use spark_sdk::print_title;
+use spark_sdk::spark_utils::Spark;
+use src20_sdk::token_utils::{deploy_token_contract, Asset};
+
+/*
+inputs of a cancel batched with contract calls, the contract inputs sit between the predicate
+coin and Alice's
+    ResourcePredicate { resource: Coin { amount: 40000000000, asset_id: USDC, owner: Predicate }}
+    Contract { contract_id: Proxy }
+    Contract { contract_id: Token }
+    ResourceSigned { resource: Coin { asset_id: BASE, owner: Alice }}
+ */
+#[tokio::test]
+async fn contract_inputs_test() {
+    print_title("Contract Inputs Test");
+    //--------------- WALLETS ---------------
+    let config = WalletsConfig::new(Some(5), Some(1), Some(1_000_000_000));
+    let wallets = launch_custom_provider_and_get_wallets(config, None, None)
+        .await
+        .unwrap();
+    let admin = &wallets[0];
+    let alice = &wallets[1];
+    let alice_address = Address::from(alice.address());
+
+    //--------------- TOKENS ---------------
+    let token_contract = deploy_token_contract(&admin).await;
+    let usdc = Asset::new(admin.clone(), token_contract.contract_id().into(), "USDC");
+    let btc = Asset::new(admin.clone(), token_contract.contract_id().into(), "BTC");
+
+    let quote_amount = usdc.parse_units(40_000_f64) as u64; //40k USDC
+    let base_amount = btc.parse_units(1_f64) as u64; // 1 BTC
+
+    let price_decimals = 9;
+
+    let exp = price_decimals + btc.decimals - usdc.decimals;
+    let price = (quote_amount as u128 * 10u128.pow(exp as u32) / base_amount as u128) as u64;
+
+    usdc.mint(alice_address, quote_amount).await.unwrap();
+    btc.mint(alice_address, base_amount).await.unwrap();
+
+    //--------------- PREDICATE ---------
+    let spark = Spark::deploy_proxy(admin, &btc, &usdc).await;
+    let options = OrderOptions::default().with_random_nonce();
+    let buy_predicate = spark.get_buy_predicate_with_options(alice, price, &options);
+    let sell_predicate = spark.get_sell_predicate_with_options(alice, price, &options);
+
+    for (predicate, asset_id, amount) in [
+        (&buy_predicate, usdc.asset_id, quote_amount),
+        (&sell_predicate, btc.asset_id, base_amount),
+    ] {
+        spark
+            .with_account(alice)
+            .create_order_with_options(
+                predicate.address().into(),
+                asset_id,
+                amount,
+                price,
+                &options,
+            )
+            .await
+            .unwrap();
+    }
+
+    //--------------- THE TEST ---------
+    // The contract inputs have no coin owner, the predicates have to look past them to find
+    // Alice's coin
+    let contract_ids = [
+        spark.proxy.contract_id().clone(),
+        token_contract.contract_id().clone(),
+    ];
+    let reader = spark.reader();
+    for (predicate, asset_id, amount) in [
+        (&buy_predicate, usdc.asset_id, quote_amount),
+        (&sell_predicate, btc.asset_id, base_amount),
+    ] {
+        let response = spark
+            .cancel_order_with_contracts(alice, predicate, asset_id, amount, &contract_ids)
+            .await
+            .unwrap();
+        // batched cancels still go through the proxy
+        let events = reader.decode_cancel_events(&response.receipts).unwrap();
+        assert_eq!(events.len(), 1);
+        assert_eq!(events[0].predicate_root, predicate.address().into());
+    }
+
+    let buy_balance = buy_predicate
+        .get_asset_balance(&usdc.asset_id)
+        .await
+        .unwrap();
+    let sell_balance = sell_predicate
+        .get_asset_balance(&btc.asset_id)
+        .await
+        .unwrap();
+    let alice_usdc_balance = alice.get_asset_balance(&usdc.asset_id).await.unwrap();
+    let alice_btc_balance = alice.get_asset_balance(&btc.asset_id).await.unwrap();
+
+    assert_eq!(buy_balance, 0);
+    assert_eq!(sell_balance, 0);
+    assert_eq!(alice_usdc_balance, quote_amount);
+    assert_eq!(alice_btc_balance, base_amount);
+}
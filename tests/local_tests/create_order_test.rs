@@ -1,6 +1,10 @@
 use fuels::test_helpers::{launch_custom_provider_and_get_wallets, WalletsConfig};
-use fuels::{prelude::ViewOnlyAccount, types::Address};
-use spark_sdk::spark_utils::Spark;
+use fuels::{
+    prelude::ViewOnlyAccount,
+    types::{Address, Identity},
+};
+use spark_sdk::order::{order_id, OrderOptions};
+use spark_sdk::spark_utils::{CreateOrderEvent, Spark};
 use spark_sdk::print_title;
 use src20_sdk::token_utils::{deploy_token_contract, Asset};
 
@@ -33,23 +37,40 @@ async fn create_order_test() {
     usdc.mint(alice_address, quote_amount).await.unwrap();
 
     let spark = Spark::deploy_proxy(admin, &btc, &usdc).await;
-    let buy_predicate = spark.get_buy_predicate(alice, &btc, &usdc, price, 1);
+    let buy_predicate = spark.get_buy_predicate(alice, price, 1);
     assert!(alice.get_asset_balance(&usdc.asset_id).await.unwrap() == quote_amount);
 
     // create_order(alice, predicate.address(), usdc.asset_id, quote_amount)
     //     .await
     //     .unwrap();
-    spark
+    let response = spark
         .with_account(alice)
         .create_order(
             buy_predicate.address().into(),
             usdc.asset_id,
             quote_amount,
             price,
+            1,
         )
         .await
         .unwrap();
 
+    // The proxy's order id can be computed offline
+    let expected_order_id = order_id(
+        &buy_predicate.address().into(),
+        &Identity::Address(alice_address),
+        price,
+        &btc.asset_id,
+        &usdc.asset_id,
+        &OrderOptions::default().nonce,
+        quote_amount,
+    );
+    let events = response
+        .decode_logs_with_type::<CreateOrderEvent>()
+        .unwrap();
+    assert_eq!(response.value, expected_order_id);
+    assert_eq!(events[0].order_id, expected_order_id);
+
     assert!(alice.get_asset_balance(&usdc.asset_id).await.unwrap() == 0);
     let predicate_balance = buy_predicate
         .get_asset_balance(&usdc.asset_id)
@@ -57,3 +78,63 @@ async fn create_order_test() {
         .unwrap();
     assert!(predicate_balance == quote_amount);
 }
+
+// The order is logged with the min-fill its predicate was built with
+#[tokio::test]
+async fn create_order_min_fulfill_test() {
+    print_title("Create Order Min Fulfill Test");
+    let config = WalletsConfig::new(Some(5), Some(1), Some(1_000_000_000));
+    let wallets = launch_custom_provider_and_get_wallets(config, None, None)
+        .await
+        .unwrap();
+    let admin = &wallets[0];
+    let alice = &wallets[1];
+    let alice_address = Address::from(alice.address());
+
+    let token_contract = deploy_token_contract(&admin).await;
+    let usdc = Asset::new(admin.clone(), token_contract.contract_id().into(), "USDC");
+    let btc = Asset::new(admin.clone(), token_contract.contract_id().into(), "BTC");
+
+    let quote_amount = usdc.parse_units(40_000_f64) as u64; //40k USDC
+    let base_amount = btc.parse_units(1_f64) as u64; // 1 BTC
+    let exp = 9 + btc.decimals - usdc.decimals;
+    let price = (quote_amount as u128 * 10u128.pow(exp as u32) / base_amount as u128) as u64;
+    let min_fulfill = usdc.parse_units(100_f64) as u64;
+
+    usdc.mint(alice_address, quote_amount).await.unwrap();
+
+    let spark = Spark::deploy_proxy(admin, &btc, &usdc).await;
+    let buy_predicate = spark.get_buy_predicate(alice, price, min_fulfill);
+
+    // Logging the default min-fill would describe another predicate
+    let res = spark
+        .with_account(alice)
+        .create_order(
+            buy_predicate.address().into(),
+            usdc.asset_id,
+            quote_amount,
+            price,
+            1,
+        )
+        .await;
+    assert!(res
+        .unwrap_err()
+        .to_string()
+        .contains("PredicateRootMismatch"));
+
+    let response = spark
+        .with_account(alice)
+        .create_order(
+            buy_predicate.address().into(),
+            usdc.asset_id,
+            quote_amount,
+            price,
+            min_fulfill,
+        )
+        .await
+        .unwrap();
+    let events = response
+        .decode_logs_with_type::<CreateOrderEvent>()
+        .unwrap();
+    assert_eq!(events[0].min_fulfill_amount, min_fulfill);
+}
@@ -0,0 +1,191 @@
+use fuels::test_helpers::{launch_custom_provider_and_get_wallets, WalletsConfig};
+use fuels::{prelude::ViewOnlyAccount, types::Address};
+use spark_sdk::auction::DutchAuction;
+use spark_sdk::order::random_nonce;
+use spark_sdk::print_title;
+use spark_sdk::spark_utils::{CreateAuctionEvent, Spark};
+use src20_sdk::token_utils::{deploy_token_contract, Asset};
+
+// Alice auctions 1 BTC from 50k down to 30k USDC over 100 blocks, Bob buys half right away
+// and the rest 50 blocks later
+#[tokio::test]
+async fn dutch_auction_test() {
+    print_title("Dutch Auction Test");
+    //--------------- WALLETS ---------------
+    let config = WalletsConfig::new(Some(5), Some(1), Some(1_000_000_000));
+    let wallets = launch_custom_provider_and_get_wallets(config, None, None)
+        .await
+        .unwrap();
+    let admin = &wallets[0];
+    let alice = &wallets[1];
+    let alice_address = Address::from(alice.address());
+    let bob = wallets[2].clone();
+    let bob_address = Address::from(bob.address());
+    let provider = admin.try_provider().unwrap().clone();
+
+    //--------------- TOKENS ---------------
+    let token_contract = deploy_token_contract(&admin).await;
+    let usdc = Asset::new(admin.clone(), token_contract.contract_id().into(), "USDC");
+    let btc = Asset::new(admin.clone(), token_contract.contract_id().into(), "BTC");
+    let (base_decimals, quote_decimals) = (btc.decimals as u32, usdc.decimals as u32);
+
+    let base_amount = btc.parse_units(1_f64) as u64; // 1 BTC
+
+    let price_decimals = 9;
+
+    let exp = price_decimals + btc.decimals - usdc.decimals;
+    let price_of = |usd: f64| {
+        (usdc.parse_units(usd) as u128 * 10u128.pow(exp as u32) / base_amount as u128) as u64
+    };
+
+    btc.mint(alice_address, base_amount).await.unwrap();
+    usdc.mint(bob_address, usdc.parse_units(50_000_f64) as u64)
+        .await
+        .unwrap();
+
+    //--------------- PREDICATE ---------
+    let spark = Spark::deploy_proxy(admin, &btc, &usdc).await;
+    let start_block = provider.latest_block_height().await.unwrap();
+    let auction = DutchAuction::new(
+        price_of(50_000_f64),
+        price_of(30_000_f64),
+        start_block,
+        start_block + 100,
+    );
+    assert!(auction.is_valid());
+    let nonce = random_nonce();
+    let predicate = spark.get_dutch_auction_predicate(alice, &auction, nonce);
+
+    let response = spark
+        .with_account(alice)
+        .create_auction(
+            predicate.address().into(),
+            btc.asset_id,
+            base_amount,
+            &auction,
+            nonce,
+        )
+        .await
+        .unwrap();
+    let events = response
+        .decode_logs_with_type::<CreateAuctionEvent>()
+        .unwrap();
+    assert_eq!(events.len(), 1);
+    assert_eq!(events[0].start_price, auction.start_price);
+    assert_eq!(events[0].end_block, auction.end_block);
+    assert_eq!(events[0].base_amount, base_amount);
+
+    //--------------- THE TEST ---------
+    let height = provider.latest_block_height().await.unwrap();
+    let first_quote =
+        auction.quote_amount_at(base_amount / 2, height, base_decimals, quote_decimals);
+    spark
+        .fulfill_auction(
+            &bob,
+            &predicate,
+            alice.address(),
+            btc.asset_id,
+            base_amount / 2,
+            usdc.asset_id,
+            first_quote,
+            height,
+        )
+        .await
+        .unwrap();
+
+    provider.produce_blocks(50, None).await.unwrap();
+    let height = provider.latest_block_height().await.unwrap();
+    assert!(auction.current_price(&provider).await.unwrap() < auction.price_at(start_block + 10));
+
+    // The end price isn't there yet
+    let end_quote = auction.quote_amount_at(
+        base_amount / 2,
+        auction.end_block,
+        base_decimals,
+        quote_decimals,
+    );
+    let res = spark
+        .fulfill_auction(
+            &bob,
+            &predicate,
+            alice.address(),
+            btc.asset_id,
+            base_amount / 2,
+            usdc.asset_id,
+            end_quote,
+            height,
+        )
+        .await;
+    assert!(res.is_err());
+
+    // and claiming a later block keeps the tx out until that block
+    let res = spark
+        .fulfill_auction(
+            &bob,
+            &predicate,
+            alice.address(),
+            btc.asset_id,
+            base_amount / 2,
+            usdc.asset_id,
+            end_quote,
+            auction.end_block,
+        )
+        .await;
+    assert!(res.is_err());
+
+    let second_quote =
+        auction.quote_amount_at(base_amount / 2, height, base_decimals, quote_decimals);
+    assert!(second_quote < first_quote);
+    spark
+        .fulfill_auction(
+            &bob,
+            &predicate,
+            alice.address(),
+            btc.asset_id,
+            base_amount / 2,
+            usdc.asset_id,
+            second_quote,
+            height,
+        )
+        .await
+        .unwrap();
+
+    let predicate_balance = predicate.get_asset_balance(&btc.asset_id).await.unwrap();
+    let alice_usdc_balance = alice.get_asset_balance(&usdc.asset_id).await.unwrap();
+    let bob_btc_balance = bob.get_asset_balance(&btc.asset_id).await.unwrap();
+
+    assert_eq!(predicate_balance, 0);
+    assert_eq!(alice_usdc_balance, first_quote + second_quote);
+    assert_eq!(bob_btc_balance, base_amount);
+}
+
+#[test]
+fn dutch_auction_price_test() {
+    let auction = DutchAuction::new(50_000, 30_000, 100, 200);
+    assert!(auction.is_valid());
+    assert!(!DutchAuction::new(30_000, 50_000, 100, 200).is_valid());
+    assert!(!DutchAuction::new(50_000, 30_000, 200, 200).is_valid());
+
+    assert_eq!(auction.price_at(0), 50_000);
+    assert_eq!(auction.price_at(100), 50_000);
+    assert_eq!(auction.price_at(150), 40_000);
+    assert_eq!(auction.price_at(133), 43_400);
+    assert_eq!(auction.price_at(200), 30_000);
+    assert_eq!(auction.price_at(1_000), 30_000);
+
+    // 1 BTC (8 decimals) for USDC (6 decimals), prices with 9 decimals
+    let auction = DutchAuction::new(50_000_000_000_000, 30_000_000_000_000, 100, 200);
+    assert_eq!(
+        auction.quote_amount_at(100_000_000, 150, 8, 6),
+        40_000_000_000
+    );
+    assert_eq!(
+        auction.base_amount_at(40_000_000_000, 150, 8, 6),
+        100_000_000
+    );
+    assert_eq!(auction.quote_amount_at(1, 150, 8, 6), 400);
+    // rounded up, the maker never gets less than the price
+    let auction = DutchAuction::new(50_000, 30_000, 100, 200);
+    assert_eq!(auction.quote_amount_at(1, 150, 6, 6), 1);
+    assert_eq!(auction.base_amount_at(1, 150, 6, 6), 25_000);
+}
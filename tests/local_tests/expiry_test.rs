@@ -0,0 +1,176 @@
+use fuels::test_helpers::{launch_custom_provider_and_get_wallets, WalletsConfig};
+use fuels::{prelude::ViewOnlyAccount, types::Address};
+use spark_sdk::order::{Expiry, OrderOptions, TAI64_UNIX_EPOCH};
+use spark_sdk::print_title;
+use spark_sdk::spark_utils::{CreateOrderEvent, Spark};
+use src20_sdk::token_utils::{deploy_token_contract, Asset};
+
+#[tokio::test]
+async fn expiry_test() {
+    print_title("Expiry Test");
+    //--------------- WALLETS ---------------
+    let config = WalletsConfig::new(Some(5), Some(1), Some(1_000_000_000));
+    let wallets = launch_custom_provider_and_get_wallets(config, None, None)
+        .await
+        .unwrap();
+    let admin = &wallets[0];
+    let alice = &wallets[1];
+    let alice_address = Address::from(alice.address());
+    let bob = wallets[2].clone();
+    let bob_address = Address::from(bob.address());
+    let provider = admin.try_provider().unwrap().clone();
+
+    //--------------- TOKENS ---------------
+    let token_contract = deploy_token_contract(&admin).await;
+    let usdc = Asset::new(admin.clone(), token_contract.contract_id().into(), "USDC");
+    let btc = Asset::new(admin.clone(), token_contract.contract_id().into(), "BTC");
+
+    let quote_amount = usdc.parse_units(40_000_f64) as u64; //40k USDC
+    let base_amount = btc.parse_units(1_f64) as u64; // 1 BTC
+
+    let price_decimals = 9;
+
+    let exp = price_decimals + btc.decimals - usdc.decimals;
+    let price = (quote_amount as u128 * 10u128.pow(exp as u32) / base_amount as u128) as u64;
+
+    usdc.mint(alice_address, quote_amount).await.unwrap();
+    btc.mint(bob_address, base_amount).await.unwrap();
+
+    //--------------- PREDICATE ---------
+    let spark = Spark::deploy_proxy(admin, &btc, &usdc).await;
+    let expiry = Expiry::BlockHeight(provider.latest_block_height().await.unwrap() + 10);
+    let options = OrderOptions::default().with_expiry(expiry);
+    let buy_predicate = spark.get_buy_predicate_with_options(alice, price, &options);
+    let root = buy_predicate.address();
+
+    // Same terms without an expiry is a different order
+    let gtc_predicate = spark.get_buy_predicate(alice, price, 1);
+    assert_ne!(gtc_predicate.address(), root);
+
+    let response = spark
+        .with_account(alice)
+        .create_order_with_options(root.into(), usdc.asset_id, quote_amount, price, &options)
+        .await
+        .unwrap();
+    let events = response
+        .decode_logs_with_type::<CreateOrderEvent>()
+        .unwrap();
+    assert_eq!(events.len(), 1);
+    assert_eq!(events[0].expiry, expiry.to_configurable());
+
+    // Before the expiry fills go through the expiry script
+    spark
+        .fulfill_order_with_options(
+            &bob,
+            &buy_predicate,
+            alice.address(),
+            usdc.asset_id,
+            quote_amount / 2,
+            btc.asset_id,
+            base_amount / 2,
+            &options,
+        )
+        .await
+        .unwrap();
+
+    // A fill without the expiry script is rejected by the predicate
+    let no_script = spark
+        .build_fulfill_order(
+            &bob,
+            &buy_predicate,
+            alice.address(),
+            usdc.asset_id,
+            quote_amount / 4,
+            btc.asset_id,
+            base_amount / 4,
+            &OrderOptions::default(),
+        )
+        .await
+        .unwrap();
+    assert!(provider
+        .send_transaction_and_await_commit(no_script)
+        .await
+        .and_then(|status| status.take_receipts_checked(None))
+        .is_err());
+
+    provider.produce_blocks(20, None).await.unwrap();
+    assert!(expiry.is_expired_on(&provider).await.unwrap());
+
+    // The SDK refuses to fill...
+    let res = spark
+        .fulfill_order_with_options(
+            &bob,
+            &buy_predicate,
+            alice.address(),
+            usdc.asset_id,
+            quote_amount / 2,
+            btc.asset_id,
+            base_amount / 2,
+            &options,
+        )
+        .await;
+    assert!(res.is_err());
+
+    // ...and so does the chain
+    let expired_fill = spark
+        .build_fulfill_order(
+            &bob,
+            &buy_predicate,
+            alice.address(),
+            usdc.asset_id,
+            quote_amount / 2,
+            btc.asset_id,
+            base_amount / 2,
+            &options,
+        )
+        .await
+        .unwrap();
+    assert!(provider
+        .send_transaction_and_await_commit(expired_fill)
+        .await
+        .and_then(|status| status.take_receipts_checked(None))
+        .is_err());
+
+    // The maker can still take the rest back
+    spark
+        .cancel_order(alice, &buy_predicate, usdc.asset_id, quote_amount / 2)
+        .await
+        .unwrap();
+
+    let predicate_usdc_balance = buy_predicate
+        .get_asset_balance(&usdc.asset_id)
+        .await
+        .unwrap();
+    let alice_btc_balance = alice.get_asset_balance(&btc.asset_id).await.unwrap();
+    let alice_usdc_balance = alice.get_asset_balance(&usdc.asset_id).await.unwrap();
+    let bob_usdc_balance = bob.get_asset_balance(&usdc.asset_id).await.unwrap();
+
+    assert_eq!(predicate_usdc_balance, 0);
+    assert_eq!(alice_btc_balance, base_amount / 2);
+    assert_eq!(alice_usdc_balance, quote_amount / 2);
+    assert_eq!(bob_usdc_balance, quote_amount / 2);
+}
+
+#[test]
+fn expiry_configurable_test() {
+    assert_eq!(Expiry::Never.to_configurable(), 0);
+    assert_eq!(Expiry::BlockHeight(1_000).to_configurable(), 1_000);
+    assert_eq!(
+        Expiry::Timestamp(1_700_000_000).to_configurable(),
+        TAI64_UNIX_EPOCH + 1_700_000_000
+    );
+
+    for expiry in [
+        Expiry::Never,
+        Expiry::BlockHeight(1_000),
+        Expiry::Timestamp(1_700_000_000),
+    ] {
+        assert_eq!(Expiry::from_configurable(expiry.to_configurable()), expiry);
+    }
+
+    assert!(!Expiry::BlockHeight(100).is_expired(100, 0));
+    assert!(Expiry::BlockHeight(100).is_expired(101, 0));
+    assert!(!Expiry::Timestamp(1_700_000_000).is_expired(0, 1_700_000_000));
+    assert!(Expiry::Timestamp(1_700_000_000).is_expired(0, 1_700_000_001));
+    assert!(!Expiry::Never.is_expired(u32::MAX, u64::MAX));
+}
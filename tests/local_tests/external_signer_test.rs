@@ -0,0 +1,100 @@
+use fuels::crypto::SecretKey;
+use fuels::prelude::{TxPolicies, BASE_ASSET_ID};
+use fuels::test_helpers::{launch_custom_provider_and_get_wallets, WalletsConfig};
+use fuels::{prelude::ViewOnlyAccount, types::Address};
+use spark_sdk::print_title;
+use spark_sdk::signer::{RemoteSigner, SignerAccount};
+use spark_sdk::spark_utils::{Spark, SparkReader};
+use src20_sdk::token_utils::{deploy_token_contract, Asset};
+
+// Alice keeps her key behind a signing service, Bob is a regular wallet
+#[tokio::test]
+async fn external_signer_test() {
+    print_title("External Signer Test");
+    //--------------- WALLETS ---------------
+    let config = WalletsConfig::new(Some(5), Some(1), Some(1_000_000_000));
+    let wallets = launch_custom_provider_and_get_wallets(config, None, None)
+        .await
+        .unwrap();
+    let admin = &wallets[0];
+    let bob = wallets[2].clone();
+    let bob_address = Address::from(bob.address());
+    let provider = admin.try_provider().unwrap().clone();
+
+    let alice_key = SecretKey::random(&mut rand::thread_rng());
+    let alice = SignerAccount::new(
+        RemoteSigner::spawn_local_service(alice_key),
+        provider.clone(),
+    );
+    let alice_address = Address::from(alice.address());
+    admin
+        .transfer(
+            alice.address(),
+            100_000_000,
+            BASE_ASSET_ID,
+            TxPolicies::default().with_gas_price(1),
+        )
+        .await
+        .unwrap();
+
+    //--------------- TOKENS ---------------
+    let token_contract = deploy_token_contract(&admin).await;
+    let usdc = Asset::new(admin.clone(), token_contract.contract_id().into(), "USDC");
+    let btc = Asset::new(admin.clone(), token_contract.contract_id().into(), "BTC");
+
+    let quote_amount = usdc.parse_units(40_000_f64) as u64; //40k USDC
+    let base_amount = btc.parse_units(1_f64) as u64; // 1 BTC
+
+    let price_decimals = 9;
+
+    let exp = price_decimals + btc.decimals - usdc.decimals;
+    let price = (quote_amount as u128 * 10u128.pow(exp as u32) / base_amount as u128) as u64;
+
+    usdc.mint(alice_address, quote_amount).await.unwrap();
+    btc.mint(bob_address, base_amount / 2).await.unwrap();
+
+    //--------------- PREDICATE ---------
+    let spark = Spark::deploy_proxy(admin, &btc, &usdc).await;
+    let buy_predicate = spark.get_buy_predicate(&alice, price, 1);
+    let root = buy_predicate.address();
+
+    spark
+        .with_account(&alice)
+        .create_order(root.into(), usdc.asset_id, quote_amount, price, 1)
+        .await
+        .unwrap();
+
+    // Nobody needs a key to look at the book
+    let reader = SparkReader::new(&provider, spark.proxy.contract_id());
+    let predicate_usdc_balance = reader.order_balance(root, usdc.asset_id).await.unwrap();
+    assert_eq!(predicate_usdc_balance, quote_amount);
+
+    spark
+        .fulfill_order(
+            &bob,
+            &buy_predicate,
+            alice.address(),
+            usdc.asset_id,
+            quote_amount / 2,
+            btc.asset_id,
+            base_amount / 2,
+        )
+        .await
+        .unwrap();
+
+    // Cancelling needs a coin signed by the maker, i.e. the signing service
+    spark
+        .cancel_order(&alice, &buy_predicate, usdc.asset_id, quote_amount / 2)
+        .await
+        .unwrap();
+
+    let predicate_usdc_balance = reader.order_balance(root, usdc.asset_id).await.unwrap();
+    let alice_btc_balance = alice.get_asset_balance(&btc.asset_id).await.unwrap();
+    let alice_usdc_balance = alice.get_asset_balance(&usdc.asset_id).await.unwrap();
+    let bob_usdc_balance = bob.get_asset_balance(&usdc.asset_id).await.unwrap();
+
+    assert_eq!(predicate_usdc_balance, 0);
+    assert_eq!(alice_btc_balance, base_amount / 2);
+    assert_eq!(alice_usdc_balance, quote_amount / 2);
+    assert_eq!(bob_usdc_balance, quote_amount / 2);
+}
@@ -0,0 +1,295 @@
+use fuels::test_helpers::{launch_custom_provider_and_get_wallets, WalletsConfig};
+use fuels::{prelude::ViewOnlyAccount, types::Address};
+use primitive_types::U256;
+use rand::Rng;
+use spark_sdk::order::{
+    decimals_scale, validate_decimals, OrderOptions, Price, MAX_SCALE_EXPONENT,
+};
+use spark_sdk::print_title;
+use spark_sdk::spark_utils::{CreateOrderEvent, Spark, PRICE_DECIMALS};
+use src20_sdk::token_utils::{deploy_token_contract, Asset};
+
+// A whole-unit base token against an 18 decimals quote token: the scale exponent is
+// 9 + 0 - 18 = -9, which the predicates and the proxy used to underflow on
+#[tokio::test]
+async fn extreme_decimals_test() {
+    print_title("Extreme Decimals Test");
+    //--------------- WALLETS ---------------
+    let config = WalletsConfig::new(Some(5), Some(1), Some(1_000_000_000));
+    let wallets = launch_custom_provider_and_get_wallets(config, None, None)
+        .await
+        .unwrap();
+    let admin = &wallets[0];
+    let alice = &wallets[1];
+    let alice_address = Address::from(alice.address());
+    let bob = wallets[2].clone();
+    let bob_address = Address::from(bob.address());
+
+    //--------------- TOKENS ---------------
+    let token_contract = deploy_token_contract(&admin).await;
+    let mut usdc = Asset::new(admin.clone(), token_contract.contract_id().into(), "USDC");
+    let mut btc = Asset::new(admin.clone(), token_contract.contract_id().into(), "BTC");
+    btc.decimals = 0;
+    usdc.decimals = 18;
+    let (base_decimals, quote_decimals) = (btc.decimals as u32, usdc.decimals as u32);
+
+    // 0.001 quote per base, 10^15 quote units per base unit
+    let price = Price::scaled(1_000_000);
+    let base_amount = 1_000;
+    let quote_amount = price.quote_amount(base_amount, base_decimals, quote_decimals);
+    assert_eq!(quote_amount, 1_000_000_000_000_000_000);
+
+    btc.mint(alice_address, base_amount).await.unwrap();
+    usdc.mint(alice_address, quote_amount).await.unwrap();
+    btc.mint(bob_address, base_amount).await.unwrap();
+    usdc.mint(bob_address, quote_amount).await.unwrap();
+
+    //--------------- PREDICATE ---------
+    let spark = Spark::deploy_proxy(admin, &btc, &usdc).await;
+    let options = OrderOptions::default();
+    let sell_predicate = spark.get_sell_predicate_with_options(alice, price, &options);
+    let buy_predicate = spark.get_buy_predicate_with_options(alice, price, &options);
+
+    spark
+        .with_account(alice)
+        .create_order_with_options(
+            sell_predicate.address().into(),
+            btc.asset_id,
+            base_amount,
+            price,
+            &options,
+        )
+        .await
+        .unwrap();
+
+    // The proxy converts a buy order's quote to base the same way the SDK does
+    let response = spark
+        .with_account(alice)
+        .create_order_with_options(
+            buy_predicate.address().into(),
+            usdc.asset_id,
+            quote_amount,
+            price,
+            &options,
+        )
+        .await
+        .unwrap();
+    let events = response
+        .decode_logs_with_type::<CreateOrderEvent>()
+        .unwrap();
+    assert_eq!(
+        events[0].base_amount,
+        price.base_amount(quote_amount, base_decimals, quote_decimals)
+    );
+    assert_eq!(events[0].base_amount, base_amount);
+
+    //--------------- THE TEST ---------
+    // One unit short of the SDK's quote and the sell predicate refuses
+    let one_base = price.quote_amount(1, base_decimals, quote_decimals);
+    let res = spark
+        .fulfill_order(
+            &bob,
+            &sell_predicate,
+            alice.address(),
+            btc.asset_id,
+            1,
+            usdc.asset_id,
+            one_base - 1,
+        )
+        .await;
+    assert!(res.is_err());
+
+    spark
+        .fulfill_order(
+            &bob,
+            &sell_predicate,
+            alice.address(),
+            btc.asset_id,
+            1,
+            usdc.asset_id,
+            one_base,
+        )
+        .await
+        .unwrap();
+
+    let rest = base_amount - 1;
+    spark
+        .fulfill_order(
+            &bob,
+            &sell_predicate,
+            alice.address(),
+            btc.asset_id,
+            rest,
+            usdc.asset_id,
+            price.quote_amount(rest, base_decimals, quote_decimals),
+        )
+        .await
+        .unwrap();
+
+    // Bob's base buys Alice's whole buy order
+    spark
+        .fulfill_order(
+            &bob,
+            &buy_predicate,
+            alice.address(),
+            usdc.asset_id,
+            quote_amount,
+            btc.asset_id,
+            base_amount,
+        )
+        .await
+        .unwrap();
+
+    let sell_balance = sell_predicate
+        .get_asset_balance(&btc.asset_id)
+        .await
+        .unwrap();
+    let buy_balance = buy_predicate
+        .get_asset_balance(&usdc.asset_id)
+        .await
+        .unwrap();
+    let alice_btc_balance = alice.get_asset_balance(&btc.asset_id).await.unwrap();
+    let alice_usdc_balance = alice.get_asset_balance(&usdc.asset_id).await.unwrap();
+
+    assert_eq!(sell_balance, 0);
+    assert_eq!(buy_balance, 0);
+    assert_eq!(alice_btc_balance, base_amount);
+    assert_eq!(alice_usdc_balance, quote_amount);
+}
+
+// predicate-sell's and predicate-buy's price checks, step by step the way the Sway code does them
+fn sway_price_fraction(price: Price, base_decimals: u32, quote_decimals: u32) -> (U256, U256) {
+    let pow10 = |exp: u32| (0..exp).fold(U256::one(), |acc, _| acc * U256::from(10));
+    let (price, price_den) = price.to_configurables();
+    if price_den != 0 {
+        return (U256::from(price), U256::from(price_den));
+    }
+    let up = base_decimals + PRICE_DECIMALS;
+    if up >= quote_decimals {
+        (U256::from(price), pow10(up - quote_decimals))
+    } else {
+        (U256::from(price) * pow10(quote_decimals - up), U256::one())
+    }
+}
+
+fn sell_accepts(price: Price, bd: u32, qd: u32, base_out: u64, quote_out: u64) -> bool {
+    let (num, den) = sway_price_fraction(price, bd, qd);
+    U256::from(quote_out) * den >= U256::from(base_out) * num
+}
+
+fn buy_accepts(price: Price, bd: u32, qd: u32, quote_out: u64, base_out: u64) -> bool {
+    let (num, den) = sway_price_fraction(price, bd, qd);
+    U256::from(base_out) * num >= U256::from(quote_out) * den
+}
+
+// The SDK's amounts are the least the predicates accept, for any decimals it lets through
+#[test]
+fn price_math_property_test() {
+    let mut rng = rand::thread_rng();
+    let edges = [
+        1,
+        2,
+        3,
+        10,
+        999_999_999,
+        u32::MAX as u64,
+        u64::MAX - 1,
+        u64::MAX,
+    ];
+    let pick = |rng: &mut rand::rngs::ThreadRng| {
+        if rng.gen_bool(0.5) {
+            edges[rng.gen_range(0..edges.len())]
+        } else {
+            rng.gen_range(1..=u64::MAX)
+        }
+    };
+
+    for _ in 0..10_000 {
+        let base_decimals = rng.gen_range(0..=30);
+        let quote_decimals = rng.gen_range(0..=50);
+        if validate_decimals(PRICE_DECIMALS, base_decimals, quote_decimals).is_err() {
+            continue;
+        }
+        let num = pick(&mut rng);
+        let price = if rng.gen_bool(0.5) {
+            Price::scaled(num)
+        } else {
+            Price::new(num, pick(&mut rng)).unwrap()
+        };
+        let amount = pick(&mut rng);
+
+        match price.checked_quote_amount(amount, base_decimals, quote_decimals) {
+            Some(quote) => {
+                assert!(sell_accepts(
+                    price,
+                    base_decimals,
+                    quote_decimals,
+                    amount,
+                    quote
+                ));
+                assert!(!sell_accepts(
+                    price,
+                    base_decimals,
+                    quote_decimals,
+                    amount,
+                    quote - 1
+                ));
+            }
+            None => assert!(!sell_accepts(
+                price,
+                base_decimals,
+                quote_decimals,
+                amount,
+                u64::MAX
+            )),
+        }
+
+        match price.checked_base_amount(amount, base_decimals, quote_decimals) {
+            Some(base) => {
+                assert!(buy_accepts(
+                    price,
+                    base_decimals,
+                    quote_decimals,
+                    amount,
+                    base
+                ));
+                if base > 0 {
+                    assert!(!buy_accepts(
+                        price,
+                        base_decimals,
+                        quote_decimals,
+                        amount,
+                        base - 1
+                    ));
+                }
+            }
+            None => assert!(!buy_accepts(
+                price,
+                base_decimals,
+                quote_decimals,
+                amount,
+                u64::MAX
+            )),
+        }
+    }
+}
+
+#[test]
+fn decimals_validation_test() {
+    let max = MAX_SCALE_EXPONENT;
+    assert!(validate_decimals(9, 8, 6).is_ok());
+    assert!(validate_decimals(9, 0, 18).is_ok());
+    assert!(validate_decimals(9, max - 9, 0).is_ok());
+    assert!(validate_decimals(9, max - 8, 0).is_err());
+    assert!(validate_decimals(9, 0, max + 9).is_ok());
+    assert!(validate_decimals(9, 0, max + 10).is_err());
+
+    // At the edges the predicates' products still fit in 256 bits
+    for (base_decimals, quote_decimals) in [(max - 9, 0), (0, max + 9)] {
+        let (up, down) = decimals_scale(9, base_decimals, quote_decimals);
+        let widest = U256::from(u64::MAX) * U256::from(u64::MAX);
+        assert!(widest.checked_mul(up.max(down)).is_some());
+    }
+    assert_eq!(decimals_scale(9, 0, 18), (U256::one(), U256::exp10(9)));
+    assert_eq!(decimals_scale(9, 8, 6), (U256::exp10(11), U256::one()));
+}
@@ -0,0 +1,115 @@
+use fuels::test_helpers::{launch_custom_provider_and_get_wallets, WalletsConfig};
+use fuels::{prelude::ViewOnlyAccount, types::Address};
+use spark_sdk::order::{OrderFee, OrderOptions};
+use spark_sdk::print_title;
+use spark_sdk::spark_utils::{predicate_options, CreateOrderEvent, OrderType, Spark};
+use src20_sdk::token_utils::{deploy_token_contract, Asset};
+
+/*
+outputs of a fill with a fee
+    Coin { to: Alice, amount: 100000000, asset_id: BTC }
+    Change { to: Bob, amount: 0, asset_id: BTC }
+    Coin { to: Bob, amount: 40000000000, asset_id: USDC }
+    Change { to: Predicate, amount: 0, asset_id: USDC }
+    Coin { to: Treasury, amount: 300000, asset_id: BTC }
+ */
+#[tokio::test]
+async fn fee_test() {
+    print_title("Fee Test");
+    //--------------- WALLETS ---------------
+    let config = WalletsConfig::new(Some(5), Some(1), Some(1_000_000_000));
+    let wallets = launch_custom_provider_and_get_wallets(config, None, None)
+        .await
+        .unwrap();
+    let admin = &wallets[0];
+    let alice = &wallets[1];
+    let alice_address = Address::from(alice.address());
+    let bob = wallets[2].clone();
+    let bob_address = Address::from(bob.address());
+    let treasury = &wallets[3];
+    let treasury_address = Address::from(treasury.address());
+
+    //--------------- TOKENS ---------------
+    let token_contract = deploy_token_contract(&admin).await;
+    let usdc = Asset::new(admin.clone(), token_contract.contract_id().into(), "USDC");
+    let btc = Asset::new(admin.clone(), token_contract.contract_id().into(), "BTC");
+
+    let quote_amount = usdc.parse_units(40_000_f64) as u64; //40k USDC
+    let base_amount = btc.parse_units(1_f64) as u64; // 1 BTC
+
+    let price_decimals = 9;
+
+    let exp = price_decimals + btc.decimals - usdc.decimals;
+    let price = (quote_amount as u128 * 10u128.pow(exp as u32) / base_amount as u128) as u64;
+
+    let fee = OrderFee::new(treasury_address, 30); // 0.3%
+    let fee_amount = fee.fee_for(base_amount);
+    assert_eq!(fee_amount, base_amount * 3 / 1000);
+
+    usdc.mint(alice_address, quote_amount).await.unwrap();
+    btc.mint(bob_address, base_amount + fee_amount)
+        .await
+        .unwrap();
+
+    //--------------- PREDICATE ---------
+    let spark = Spark::deploy_proxy(admin, &btc, &usdc).await;
+    let options = OrderOptions::default().with_fee(fee);
+    let buy_predicate = spark.get_buy_predicate_with_options(alice, price, &options);
+    let root = buy_predicate.address();
+
+    let response = spark
+        .with_account(alice)
+        .create_order_with_options(root.into(), usdc.asset_id, quote_amount, price, &options)
+        .await
+        .unwrap();
+    let events = response
+        .decode_logs_with_type::<CreateOrderEvent>()
+        .unwrap();
+    assert_eq!(events[0].fee_recipient, treasury_address);
+    assert_eq!(events[0].fee_bps, 30);
+
+    // Without the fee output the predicate doesn't let the coin go
+    let res = spark
+        .fulfill_order_with_options(
+            &bob,
+            &buy_predicate,
+            alice.address(),
+            usdc.asset_id,
+            quote_amount,
+            btc.asset_id,
+            base_amount,
+            &OrderOptions::default(),
+        )
+        .await;
+    assert!(res.is_err());
+
+    // The plain fill reads the fee terms off the predicate and adds the fee output itself
+    assert_eq!(predicate_options(&buy_predicate, &OrderType::BUY), options);
+    spark
+        .fulfill_order(
+            &bob,
+            &buy_predicate,
+            alice.address(),
+            usdc.asset_id,
+            quote_amount,
+            btc.asset_id,
+            base_amount,
+        )
+        .await
+        .unwrap();
+
+    let predicate_balance = buy_predicate
+        .get_asset_balance(&usdc.asset_id)
+        .await
+        .unwrap();
+    let bob_btc_balance = bob.get_asset_balance(&btc.asset_id).await.unwrap();
+    let bob_usdc_balance = bob.get_asset_balance(&usdc.asset_id).await.unwrap();
+    let alice_btc_balance = alice.get_asset_balance(&btc.asset_id).await.unwrap();
+    let treasury_btc_balance = treasury.get_asset_balance(&btc.asset_id).await.unwrap();
+
+    assert_eq!(predicate_balance, 0);
+    assert_eq!(bob_btc_balance, 0);
+    assert_eq!(bob_usdc_balance, quote_amount);
+    assert_eq!(alice_btc_balance, base_amount);
+    assert_eq!(treasury_btc_balance, fee_amount);
+}
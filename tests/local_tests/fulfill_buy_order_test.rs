@@ -49,7 +49,7 @@ async fn fulfill_buy_order_test() {
 
     //--------------- PREDICATE ---------
     let spark = Spark::deploy_proxy(admin, &btc, &usdc).await;
-    let buy_predicate = spark.get_buy_predicate(alice, &btc, &usdc, price, 1);
+    let buy_predicate = spark.get_buy_predicate(alice, price, 1);
     let root = buy_predicate.address();
 
     let initial_bob_btc_balance = bob.get_asset_balance(&btc.asset_id).await.unwrap();
@@ -64,7 +64,7 @@ async fn fulfill_buy_order_test() {
 
     spark
         .with_account(alice)
-        .create_order(root.into(), usdc.asset_id, quote_amount, price)
+        .create_order(root.into(), usdc.asset_id, quote_amount, price, 1)
         .await
         .unwrap();
 
@@ -137,7 +137,7 @@ async fn partial_fulfill_buy_order_test() {
 
     //--------------- PREDICATE ---------
     let spark = Spark::deploy_proxy(admin, &btc, &usdc).await;
-    let buy_predicate = spark.get_buy_predicate(alice, &btc, &usdc, price, 1);
+    let buy_predicate = spark.get_buy_predicate(alice, price, 1);
     let root = buy_predicate.address();
 
     let initial_bob_btc_balance = bob.get_asset_balance(&btc.asset_id).await.unwrap();
@@ -152,7 +152,7 @@ async fn partial_fulfill_buy_order_test() {
 
     spark
         .with_account(alice)
-        .create_order(root.into(), usdc.asset_id, quote_amount, price)
+        .create_order(root.into(), usdc.asset_id, quote_amount, price, 1)
         .await
         .unwrap();
 
@@ -36,7 +36,7 @@ async fn fulfill_sell_order_test() {
 
     //--------------- PREDICATE ---------
     let spark = Spark::deploy_proxy(admin, &btc, &usdc).await;
-    let sell_predicate = spark.get_sell_predicate(alice, &btc, &usdc, price, 1);
+    let sell_predicate = spark.get_sell_predicate(alice, price, 1);
     let root = sell_predicate.address();
 
     let initial_bob_btc_balance = bob.get_asset_balance(&btc.asset_id).await.unwrap();
@@ -51,7 +51,7 @@ async fn fulfill_sell_order_test() {
 
     spark
         .with_account(alice)
-        .create_order(root.into(), btc.asset_id, base_amount, price)
+        .create_order(root.into(), btc.asset_id, base_amount, price, 1)
         .await
         .unwrap();
 
@@ -123,7 +123,7 @@ async fn partial_fulfill_sell_order_test() {
 
     //--------------- PREDICATE ---------
     let spark = Spark::deploy_proxy(admin, &btc, &usdc).await;
-    let sell_predicate = spark.get_sell_predicate(alice, &btc, &usdc, price, 1);
+    let sell_predicate = spark.get_sell_predicate(alice, price, 1);
     let root = sell_predicate.address();
 
     let initial_bob_btc_balance = bob.get_asset_balance(&btc.asset_id).await.unwrap();
@@ -138,7 +138,7 @@ async fn partial_fulfill_sell_order_test() {
 
     spark
         .with_account(alice)
-        .create_order(root.into(), btc.asset_id, base_amount, price)
+        .create_order(root.into(), btc.asset_id, base_amount, price, 1)
         .await
         .unwrap();
 
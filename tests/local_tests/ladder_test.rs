@@ -0,0 +1,225 @@
+use fuels::prelude::{Account, TxPolicies};
+use fuels::test_helpers::{launch_custom_provider_and_get_wallets, WalletsConfig};
+use fuels::{prelude::ViewOnlyAccount, types::Address};
+use spark_sdk::ladder::{LadderLevel, PriceLadder};
+use spark_sdk::order::random_nonce;
+use spark_sdk::print_title;
+use spark_sdk::spark_utils::{CreateLadderOrderEvent, Spark};
+use src20_sdk::token_utils::{deploy_token_contract, Asset};
+
+// Alice sells 1 BTC in three levels: 0.3 at 40k, 0.3 at 41k and 0.4 at 42k USDC
+#[tokio::test]
+async fn ladder_test() {
+    print_title("Ladder Test");
+    //--------------- WALLETS ---------------
+    let config = WalletsConfig::new(Some(5), Some(1), Some(1_000_000_000));
+    let wallets = launch_custom_provider_and_get_wallets(config, None, None)
+        .await
+        .unwrap();
+    let admin = &wallets[0];
+    let alice = &wallets[1];
+    let alice_address = Address::from(alice.address());
+    let bob = wallets[2].clone();
+    let bob_address = Address::from(bob.address());
+
+    //--------------- TOKENS ---------------
+    let token_contract = deploy_token_contract(&admin).await;
+    let usdc = Asset::new(admin.clone(), token_contract.contract_id().into(), "USDC");
+    let btc = Asset::new(admin.clone(), token_contract.contract_id().into(), "BTC");
+
+    let base_amount = btc.parse_units(1_f64) as u64; // 1 BTC
+
+    let price_decimals = 9;
+
+    let exp = price_decimals + btc.decimals - usdc.decimals;
+    let price_of = |usd: f64| {
+        (usdc.parse_units(usd) as u128 * 10u128.pow(exp as u32) / base_amount as u128) as u64
+    };
+    let btc_units = |amount: f64| btc.parse_units(amount) as u64;
+    let usdc_units = |amount: f64| usdc.parse_units(amount) as u64;
+
+    btc.mint(alice_address, base_amount).await.unwrap();
+    btc.mint(bob_address, btc_units(0.1)).await.unwrap();
+    usdc.mint(bob_address, usdc_units(45_000_f64))
+        .await
+        .unwrap();
+
+    //--------------- PREDICATE ---------
+    let spark = Spark::deploy_proxy(admin, &btc, &usdc).await;
+    let ladder = PriceLadder::new(vec![
+        LadderLevel::new(price_of(40_000_f64), btc_units(0.3)),
+        LadderLevel::new(price_of(41_000_f64), btc_units(0.3)),
+        LadderLevel::new(price_of(42_000_f64), btc_units(0.4)),
+    ]);
+    assert!(ladder.is_valid());
+    assert_eq!(ladder.total_size(), base_amount);
+    let nonce = random_nonce();
+
+    // Levels out of price order are refused
+    let unordered = PriceLadder::new(ladder.levels.iter().rev().copied().collect());
+    let unordered_predicate = spark.get_ladder_predicate(alice, &unordered, nonce);
+    let res = spark
+        .with_account(alice)
+        .create_ladder_order(
+            unordered_predicate.address().into(),
+            btc.asset_id,
+            &unordered,
+            nonce,
+        )
+        .await;
+    assert!(res.is_err());
+
+    let predicate = spark.get_ladder_predicate(alice, &ladder, nonce);
+    let root = predicate.address();
+
+    let response = spark
+        .with_account(alice)
+        .create_ladder_order(root.into(), btc.asset_id, &ladder, nonce)
+        .await
+        .unwrap();
+    let events = response
+        .decode_logs_with_type::<CreateLadderOrderEvent>()
+        .unwrap();
+    assert_eq!(events.len(), 1);
+    assert_eq!(events[0].base_amount, base_amount);
+    let logged =
+        PriceLadder::from_configurables(events[0].prices, events[0].sizes, events[0].levels);
+    assert_eq!(logged, ladder);
+
+    //--------------- THE TEST ---------
+    let reader = spark.reader();
+    let quote_for = {
+        let (reader, ladder, market) = (&reader, &ladder, spark.market());
+        move |amount: u64| async move {
+            reader
+                .ladder_fill_quote(root, ladder, market, amount)
+                .await
+                .unwrap()
+        }
+    };
+
+    // Inside the first level
+    let quote = quote_for(btc_units(0.2)).await.unwrap();
+    assert_eq!(quote, usdc_units(8_000_f64));
+    spark
+        .fulfill_ladder(
+            &bob,
+            &predicate,
+            alice.address(),
+            btc.asset_id,
+            btc_units(0.2),
+            usdc.asset_id,
+            quote,
+        )
+        .await
+        .unwrap();
+
+    // A coin sent on top of the ladder's doesn't bring the first level back
+    bob.transfer(
+        root,
+        btc_units(0.1),
+        btc.asset_id,
+        TxPolicies::default().with_gas_price(1),
+    )
+    .await
+    .unwrap();
+
+    // Across the first two: 0.1 at 40k and 0.2 at 41k
+    let quote = quote_for(btc_units(0.3)).await.unwrap();
+    assert_eq!(quote, usdc_units(12_200_f64));
+    let res = spark
+        .fulfill_ladder(
+            &bob,
+            &predicate,
+            alice.address(),
+            btc.asset_id,
+            btc_units(0.3),
+            usdc.asset_id,
+            usdc_units(12_100_f64),
+        )
+        .await;
+    assert!(res.is_err());
+    spark
+        .fulfill_ladder(
+            &bob,
+            &predicate,
+            alice.address(),
+            btc.asset_id,
+            btc_units(0.3),
+            usdc.asset_id,
+            quote,
+        )
+        .await
+        .unwrap();
+
+    // There's only 0.5 left
+    assert_eq!(quote_for(btc_units(0.6)).await, None);
+    let quote = quote_for(btc_units(0.5)).await.unwrap();
+    assert_eq!(quote, usdc_units(20_900_f64));
+    spark
+        .fulfill_ladder(
+            &bob,
+            &predicate,
+            alice.address(),
+            btc.asset_id,
+            btc_units(0.5),
+            usdc.asset_id,
+            quote,
+        )
+        .await
+        .unwrap();
+
+    let predicate_balance = predicate.get_asset_balance(&btc.asset_id).await.unwrap();
+    let alice_usdc_balance = alice.get_asset_balance(&usdc.asset_id).await.unwrap();
+    let bob_btc_balance = bob.get_asset_balance(&btc.asset_id).await.unwrap();
+
+    assert_eq!(predicate_balance, btc_units(0.1));
+    assert_eq!(alice_usdc_balance, usdc_units(41_100_f64));
+    assert_eq!(bob_btc_balance, base_amount);
+}
+
+#[test]
+fn ladder_levels_test() {
+    let ladder = PriceLadder::new(vec![
+        LadderLevel::new(100, 30),
+        LadderLevel::new(110, 30),
+        LadderLevel::new(120, 40),
+    ]);
+    assert!(ladder.is_valid());
+    assert!(!PriceLadder::new(vec![]).is_valid());
+    assert!(!PriceLadder::new(vec![LadderLevel::new(100, 0)]).is_valid());
+    assert!(!PriceLadder::new(vec![LadderLevel::new(100, 1); 6]).is_valid());
+    // prices have to go up
+    assert!(
+        !PriceLadder::new(vec![LadderLevel::new(110, 30), LadderLevel::new(100, 30)]).is_valid()
+    );
+    assert!(
+        !PriceLadder::new(vec![LadderLevel::new(100, 30), LadderLevel::new(100, 30)]).is_valid()
+    );
+
+    let (prices, sizes, levels) = ladder.to_configurables();
+    assert_eq!(prices, [100, 110, 120, 0, 0]);
+    assert_eq!(sizes, [30, 30, 40, 0, 0]);
+    assert_eq!(levels, 3);
+    assert_eq!(
+        PriceLadder::from_configurables(prices, sizes, levels),
+        ladder
+    );
+
+    assert_eq!(ladder.consumed(100), 0);
+    assert_eq!(ladder.consumed(45), 55);
+    assert_eq!(
+        ladder.remaining_levels(45),
+        vec![LadderLevel::new(110, 5), LadderLevel::new(120, 40)]
+    );
+
+    // equal decimals, prices scaled by 10^9
+    assert_eq!(ladder.quote_amount(100, 30, 9, 9), Some(1));
+    assert_eq!(ladder.quote_amount(100, 1_000_000_000 / 100, 9, 9), None);
+    let ladder = PriceLadder::new(vec![
+        LadderLevel::new(1_000_000_000, 10),
+        LadderLevel::new(2_000_000_000, 10),
+    ]);
+    assert_eq!(ladder.quote_amount(20, 15, 9, 9), Some(20));
+    assert_eq!(ladder.quote_amount(5, 5, 9, 9), Some(10));
+}
@@ -0,0 +1,104 @@
+use spark_sdk::manifest::{AssetDeployment, DeploymentManifest, ManifestError, MarketDeployment};
+
+fn btc_usdc_deployment(proxy: &str) -> MarketDeployment {
+    MarketDeployment {
+        proxy: proxy.to_string(),
+        base_asset: AssetDeployment {
+            symbol: "BTC".to_string(),
+            asset_id: format!("0x{}", "11".repeat(32)),
+            decimals: 8,
+        },
+        quote_asset: AssetDeployment {
+            symbol: "USDC".to_string(),
+            asset_id: format!("0x{}", "22".repeat(32)),
+            decimals: 6,
+        },
+        price_decimals: 9,
+        buy_predicate_hash: format!("0x{}", "33".repeat(32)),
+        sell_predicate_hash: format!("0x{}", "44".repeat(32)),
+        deployer: format!("0x{}", "55".repeat(32)),
+        salt: format!("0x{}", "66".repeat(32)),
+        tx_id: None,
+        start_block: 100,
+    }
+}
+
+#[test]
+fn manifest_save_load_test() {
+    let mut manifest = DeploymentManifest::default();
+    let deployment = btc_usdc_deployment(&format!("0x{}", "aa".repeat(32)));
+    manifest.insert("local", deployment.clone()).unwrap();
+
+    let path = std::env::temp_dir().join("spark_manifest_save_load_test/deployments.json");
+    manifest.save(&path).unwrap();
+    let loaded = DeploymentManifest::load(&path).unwrap();
+
+    assert_eq!(loaded, manifest);
+    assert_eq!(loaded.get("local", "BTC", "USDC"), Some(&deployment));
+    assert_eq!(loaded.get("testnet", "BTC", "USDC"), None);
+}
+
+#[test]
+fn manifest_merge_test() {
+    let mut local = DeploymentManifest::default();
+    local
+        .insert(
+            "local",
+            btc_usdc_deployment(&format!("0x{}", "aa".repeat(32))),
+        )
+        .unwrap();
+
+    let mut testnet = DeploymentManifest::default();
+    testnet
+        .insert(
+            "testnet",
+            btc_usdc_deployment(&format!("0x{}", "bb".repeat(32))),
+        )
+        .unwrap();
+
+    local.merge(testnet.clone()).unwrap();
+    assert_eq!(local.networks.len(), 2);
+    // merging the same deployments again is a no-op
+    local.merge(testnet).unwrap();
+
+    let mut redeployed = DeploymentManifest::default();
+    redeployed
+        .insert(
+            "local",
+            btc_usdc_deployment(&format!("0x{}", "cc".repeat(32))),
+        )
+        .unwrap();
+    assert!(matches!(
+        local.merge(redeployed),
+        Err(ManifestError::Conflict { .. })
+    ));
+}
+
+#[test]
+fn manifest_validation_test() {
+    let mut manifest = DeploymentManifest::default();
+
+    let bad_proxy = btc_usdc_deployment("0x1234");
+    assert!(matches!(
+        manifest.insert("local", bad_proxy),
+        Err(ManifestError::InvalidField { field: "proxy", .. })
+    ));
+
+    let mut same_assets = btc_usdc_deployment(&format!("0x{}", "aa".repeat(32)));
+    same_assets.quote_asset.asset_id = same_assets.base_asset.asset_id.clone();
+    assert!(manifest.insert("local", same_assets).is_err());
+
+    let mut bad_decimals = btc_usdc_deployment(&format!("0x{}", "aa".repeat(32)));
+    // quote decimals past base + price decimals are fine, as long as the scale fits
+    bad_decimals.quote_asset.decimals = 18;
+    bad_decimals.price_decimals = 0;
+    assert!(bad_decimals.validate("local").is_ok());
+    bad_decimals.quote_asset.decimals = 60;
+    assert!(matches!(
+        manifest.insert("local", bad_decimals),
+        Err(ManifestError::InvalidField {
+            field: "price_decimals",
+            ..
+        })
+    ));
+}
@@ -0,0 +1,90 @@
+use fuels::prelude::ViewOnlyAccount;
+use fuels::test_helpers::{launch_custom_provider_and_get_wallets, WalletsConfig};
+use fuels::types::ContractId;
+use spark_sdk::network::NetworkProfile;
+use spark_sdk::order::OrderOptions;
+use spark_sdk::print_title;
+use spark_sdk::spark_utils::{MarketInfo, Spark, PRICE_DECIMALS};
+use src20_sdk::token_utils::{deploy_token_contract, Asset};
+use std::str::FromStr;
+
+#[tokio::test]
+async fn market_info_test() {
+    print_title("Market Info Test");
+    //--------------- WALLETS ---------------
+    let config = WalletsConfig::new(Some(5), Some(1), Some(1_000_000_000));
+    let wallets = launch_custom_provider_and_get_wallets(config, None, None)
+        .await
+        .unwrap();
+    let admin = &wallets[0];
+    let alice = &wallets[1];
+
+    //--------------- TOKENS ---------------
+    let token_contract = deploy_token_contract(&admin).await;
+    let usdc = Asset::new(admin.clone(), token_contract.contract_id().into(), "USDC");
+    let btc = Asset::new(admin.clone(), token_contract.contract_id().into(), "BTC");
+
+    //--------------- THE TEST ---------
+    let deployed = Spark::deploy_proxy(admin, &btc, &usdc).await;
+    let proxy_id = ContractId::from(deployed.proxy.contract_id()).to_string();
+
+    // A client that only knows the proxy id learns the market from it
+    let spark = Spark::new(alice, &proxy_id).await.unwrap();
+    let market = spark.market();
+    assert_eq!(market.base_asset, btc.asset_id);
+    assert_eq!(market.quote_asset, usdc.asset_id);
+    assert_eq!(market.base_asset_decimals, btc.decimals as u32);
+    assert_eq!(market.quote_asset_decimals, usdc.decimals as u32);
+    assert_eq!(market.price_decimals, PRICE_DECIMALS);
+    assert_eq!(market, deployed.market());
+    assert_eq!(*market, MarketInfo::new(&btc, &usdc));
+
+    let onchain = spark
+        .proxy
+        .methods()
+        .market_info(spark.market_id())
+        .simulate()
+        .await
+        .unwrap();
+    assert_eq!(&onchain.value, market);
+
+    // The predicates are the same as the ones built from the assets
+    let price = 70_000_000_000_000;
+    let options = OrderOptions::default().with_random_nonce();
+    let predicate = spark
+        .with_account(admin)
+        .get_sell_predicate_with_options(alice, price, &options);
+    let expected = spark.reader().get_sell_predicate_with_options(
+        alice.address(),
+        &MarketInfo::new(&btc, &usdc),
+        price,
+        &options,
+    );
+    assert_eq!(predicate.address(), expected.address());
+
+    // A contract without `market_info` is an error, not a panic
+    let token_id = ContractId::from(token_contract.contract_id()).to_string();
+    assert!(Spark::new(alice, &token_id).await.is_err());
+    assert!(Spark::new(alice, "not a contract id").await.is_err());
+
+    // The built-in testnet profile has no proxy, it has to come from spark.json or the manifest
+    let mut profile = NetworkProfile::testnet();
+    assert!(Spark::from_profile(alice, &profile, "BTC", "USDC")
+        .await
+        .is_err());
+    profile.assets.clear();
+    profile.set_proxy("BTC", "USDC", ContractId::from_str(&proxy_id).unwrap());
+    // Without the symbols' asset ids there's no telling which of the proxy's markets is meant
+    assert!(Spark::from_profile(alice, &profile, "BTC", "USDC")
+        .await
+        .is_err());
+    for (symbol, asset_id) in [("BTC", btc.asset_id), ("USDC", usdc.asset_id)] {
+        profile
+            .assets
+            .insert(symbol.to_string(), format!("0x{asset_id}"));
+    }
+    let from_profile = Spark::from_profile(alice, &profile, "BTC", "USDC")
+        .await
+        .unwrap();
+    assert_eq!(from_profile.market(), market);
+}
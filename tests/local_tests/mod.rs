@@ -1,5 +1,27 @@
+mod all_or_none_test;
 mod cancel_order_test;
+mod change_theft_test;
+mod contract_inputs_test;
 mod create_order_test;
+mod dutch_auction_test;
+mod expiry_test;
+mod external_signer_test;
+mod extreme_decimals_test;
+mod fee_test;
 mod fulfill_buy_order_test;
 mod fulfill_sell_order_test;
-// mod recreate_order_test;
+mod ladder_test;
+mod manifest_test;
+mod market_info_test;
+mod multi_market_test;
+mod network_config_test;
+mod oco_test;
+mod offline_order_test;
+mod predicate_params_test;
+mod predicate_root_test;
+mod private_order_test;
+mod proxy_fill_test;
+mod rational_price_test;
+mod recipient_test;
+mod recreate_order_test;
+mod rfq_test;
@@ -0,0 +1,153 @@
+use fuels::prelude::ViewOnlyAccount;
+use fuels::test_helpers::{launch_custom_provider_and_get_wallets, WalletsConfig};
+use fuels::types::{Address, AssetId, ContractId};
+use spark_sdk::network::NetworkProfile;
+use spark_sdk::order::OrderOptions;
+use spark_sdk::print_title;
+use spark_sdk::spark_utils::{CreateOrderEvent, MarketInfo, Spark};
+use src20_sdk::token_utils::{deploy_token_contract, Asset};
+
+// One proxy serving BTC/USDC and USDC/BTC, the second with its own tick and lot size
+#[tokio::test]
+async fn multi_market_test() {
+    print_title("Multi Market Test");
+    //--------------- WALLETS ---------------
+    let config = WalletsConfig::new(Some(5), Some(1), Some(1_000_000_000));
+    let wallets = launch_custom_provider_and_get_wallets(config, None, None)
+        .await
+        .unwrap();
+    let admin = &wallets[0];
+    let alice = &wallets[1];
+    let alice_address = Address::from(alice.address());
+
+    //--------------- TOKENS ---------------
+    let token_contract = deploy_token_contract(&admin).await;
+    let usdc = Asset::new(admin.clone(), token_contract.contract_id().into(), "USDC");
+    let btc = Asset::new(admin.clone(), token_contract.contract_id().into(), "BTC");
+
+    let base_amount = usdc.parse_units(1_f64) as u64; // 1 USDC
+    usdc.mint(alice_address, base_amount).await.unwrap();
+
+    // 0.000025 BTC per USDC
+    let exp = 9 + usdc.decimals - btc.decimals;
+    let price = (25 * 10u128.pow(exp as u32) / 1_000_000) as u64;
+
+    //--------------- MARKETS ---------
+    let spark = Spark::deploy_proxy(admin, &btc, &usdc).await;
+    assert_eq!(spark.market_id(), 0);
+
+    let tick_size = 10;
+    let lot_size = 1_000;
+    let usdc_btc_market = MarketInfo::new(&usdc, &btc)
+        .with_tick_size(tick_size)
+        .with_lot_size(lot_size);
+
+    // Only the deployer lists markets
+    let res = spark
+        .with_account(alice)
+        .list_market(&usdc_btc_market)
+        .await;
+    assert!(res.is_err());
+    let res = spark
+        .list_market(&MarketInfo::new(&usdc, &btc).with_lot_size(0))
+        .await;
+    assert!(res.is_err());
+
+    let market_id = spark.list_market(&usdc_btc_market).await.unwrap().value;
+    assert_eq!(market_id, 1);
+
+    let markets = spark.markets().await.unwrap();
+    assert_eq!(
+        markets,
+        vec![MarketInfo::new(&btc, &usdc), usdc_btc_market.clone()]
+    );
+    assert!(spark.with_market(2).await.is_err());
+
+    // A profile finds the market by its assets, and errs on a pair the proxy doesn't list
+    let mut profile = NetworkProfile::local();
+    for (symbol, asset_id) in [
+        ("USDC", usdc.asset_id),
+        ("BTC", btc.asset_id),
+        ("ETH", AssetId::BASE),
+    ] {
+        profile
+            .assets
+            .insert(symbol.to_string(), format!("0x{asset_id}"));
+    }
+    let proxy_id = ContractId::from(spark.proxy.contract_id());
+    profile.set_proxy("USDC", "BTC", proxy_id);
+    profile.set_proxy("ETH", "USDC", proxy_id);
+    let from_profile = Spark::from_profile(alice, &profile, "USDC", "BTC")
+        .await
+        .unwrap();
+    assert_eq!(from_profile.market_id(), market_id);
+    assert!(Spark::from_profile(alice, &profile, "ETH", "USDC")
+        .await
+        .is_err());
+
+    //--------------- THE TEST ---------
+    let usdc_btc_spark = spark
+        .with_account(alice)
+        .with_market(market_id)
+        .await
+        .unwrap();
+    assert_eq!(usdc_btc_spark.market(), &usdc_btc_market);
+    let options = OrderOptions::default().with_random_nonce();
+    let predicate = usdc_btc_spark.get_sell_predicate_with_options(alice, price, &options);
+
+    // The predicate is built for USDC/BTC, in BTC/USDC it would be a buy order of other terms
+    let res = spark
+        .with_account(alice)
+        .create_order_with_options(
+            predicate.address().into(),
+            usdc.asset_id,
+            base_amount,
+            price,
+            &options,
+        )
+        .await;
+    assert!(res.is_err());
+
+    // Off tick and off lot
+    let off_tick_price = price + tick_size / 2;
+    let off_tick = usdc_btc_spark.get_sell_predicate_with_options(alice, off_tick_price, &options);
+    let res = usdc_btc_spark
+        .create_order_with_options(
+            off_tick.address().into(),
+            usdc.asset_id,
+            base_amount,
+            off_tick_price,
+            &options,
+        )
+        .await;
+    assert!(res.is_err());
+    let res = usdc_btc_spark
+        .create_order_with_options(
+            predicate.address().into(),
+            usdc.asset_id,
+            base_amount - lot_size / 2,
+            price,
+            &options,
+        )
+        .await;
+    assert!(res.is_err());
+
+    let response = usdc_btc_spark
+        .create_order_with_options(
+            predicate.address().into(),
+            usdc.asset_id,
+            base_amount,
+            price,
+            &options,
+        )
+        .await
+        .unwrap();
+    let events = response
+        .decode_logs_with_type::<CreateOrderEvent>()
+        .unwrap();
+    assert_eq!(events[0].market_id, market_id);
+    assert_eq!(events[0].base_asset, usdc.asset_id);
+
+    let balance = predicate.get_asset_balance(&usdc.asset_id).await.unwrap();
+    assert_eq!(balance, base_amount);
+}
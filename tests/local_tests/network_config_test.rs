@@ -0,0 +1,50 @@
+use spark_sdk::network::{NetworkConfig, NetworkError, NetworkProfile};
+
+#[test]
+fn network_config_test() {
+    let mut config = NetworkConfig::default();
+    let mut devnet = NetworkProfile::local();
+    devnet.name = "devnet".to_string();
+    devnet
+        .assets
+        .insert("BTC".to_string(), format!("0x{}", "11".repeat(32)));
+    devnet
+        .proxies
+        .insert("BTC/USDC".to_string(), format!("0x{}", "aa".repeat(32)));
+    config.profiles.insert("devnet".to_string(), devnet);
+
+    let dir = std::env::temp_dir().join("spark_network_config_test");
+    std::fs::create_dir_all(&dir).unwrap();
+    let path = dir.join("spark.json");
+    config.save(&path).unwrap();
+    let devnet = NetworkConfig::load(&path).unwrap().profiles["devnet"].clone();
+    assert!(devnet.asset_id("BTC").unwrap().is_some());
+    assert!(devnet.asset_id("USDC").unwrap().is_none());
+    assert!(devnet.proxy("BTC", "USDC").unwrap().is_some());
+
+    // Bad input is an error, not a panic
+    assert!(matches!(
+        devnet.token_contract_id(),
+        Err(NetworkError::MissingField { .. })
+    ));
+    let mut bad_ids = devnet.clone();
+    bad_ids.token_contract_id = Some("0x1234".to_string());
+    bad_ids
+        .assets
+        .insert("USDC".to_string(), "usdc".to_string());
+    assert!(matches!(
+        bad_ids.token_contract_id(),
+        Err(NetworkError::InvalidField { .. })
+    ));
+    assert!(bad_ids.asset_id("USDC").is_err());
+
+    std::fs::write(&path, "{ \"devnet\": ").unwrap();
+    assert!(matches!(
+        NetworkConfig::load(&path),
+        Err(NetworkError::Json(_))
+    ));
+    assert!(matches!(
+        NetworkConfig::load(dir.join("missing.json")),
+        Err(NetworkError::Io(_))
+    ));
+}
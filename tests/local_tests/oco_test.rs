@@ -0,0 +1,148 @@
+use fuels::test_helpers::{launch_custom_provider_and_get_wallets, WalletsConfig};
+use fuels::{prelude::ViewOnlyAccount, types::Address};
+use spark_sdk::oco::{OcoLeg, OcoOrder};
+use spark_sdk::order::random_nonce;
+use spark_sdk::print_title;
+use spark_sdk::spark_utils::{CreateOcoOrderEvent, Spark};
+use src20_sdk::token_utils::{deploy_token_contract, Asset};
+
+// Alice exits 1 BTC at 50k (take profit) or at 35k (stop, through Carol the keeper)
+#[tokio::test]
+async fn oco_test() {
+    print_title("OCO Test");
+    //--------------- WALLETS ---------------
+    let config = WalletsConfig::new(Some(5), Some(1), Some(1_000_000_000));
+    let wallets = launch_custom_provider_and_get_wallets(config, None, None)
+        .await
+        .unwrap();
+    let admin = &wallets[0];
+    let alice = &wallets[1];
+    let alice_address = Address::from(alice.address());
+    let bob = wallets[2].clone();
+    let bob_address = Address::from(bob.address());
+    let carol = wallets[3].clone();
+    let carol_address = Address::from(carol.address());
+
+    //--------------- TOKENS ---------------
+    let token_contract = deploy_token_contract(&admin).await;
+    let usdc = Asset::new(admin.clone(), token_contract.contract_id().into(), "USDC");
+    let btc = Asset::new(admin.clone(), token_contract.contract_id().into(), "BTC");
+    let (base_decimals, quote_decimals) = (btc.decimals as u32, usdc.decimals as u32);
+
+    let base_amount = btc.parse_units(1_f64) as u64; // 1 BTC
+
+    let price_decimals = 9;
+
+    let exp = price_decimals + btc.decimals - usdc.decimals;
+    let price_of = |usd: f64| {
+        (usdc.parse_units(usd) as u128 * 10u128.pow(exp as u32) / base_amount as u128) as u64
+    };
+
+    btc.mint(alice_address, base_amount).await.unwrap();
+    usdc.mint(bob_address, usdc.parse_units(50_000_f64) as u64)
+        .await
+        .unwrap();
+    usdc.mint(carol_address, usdc.parse_units(35_000_f64) as u64)
+        .await
+        .unwrap();
+
+    //--------------- PREDICATE ---------
+    let spark = Spark::deploy_proxy(admin, &btc, &usdc).await;
+    let oco = OcoOrder::new(price_of(50_000_f64), price_of(35_000_f64), carol_address);
+    assert!(oco.is_valid());
+    let nonce = random_nonce();
+    let predicate = spark.get_oco_predicate(alice, &oco, nonce);
+
+    let response = spark
+        .with_account(alice)
+        .create_oco_order(
+            predicate.address().into(),
+            btc.asset_id,
+            base_amount,
+            &oco,
+            nonce,
+        )
+        .await
+        .unwrap();
+    let events = response
+        .decode_logs_with_type::<CreateOcoOrderEvent>()
+        .unwrap();
+    assert_eq!(events.len(), 1);
+    assert_eq!(events[0].take_profit_price, oco.take_profit_price);
+    assert_eq!(events[0].stop_price, oco.stop_price);
+    assert_eq!(events[0].stop_keeper, carol_address);
+
+    //--------------- THE TEST ---------
+    let take_profit_quote = oco.quote_amount(
+        OcoLeg::TakeProfit,
+        base_amount,
+        base_decimals,
+        quote_decimals,
+    );
+    let stop_quote = oco.quote_amount(OcoLeg::Stop, base_amount, base_decimals, quote_decimals);
+
+    // No partial fills, one leg takes everything
+    let res = spark
+        .fulfill_oco(
+            &bob,
+            &predicate,
+            alice.address(),
+            btc.asset_id,
+            base_amount / 2,
+            usdc.asset_id,
+            take_profit_quote / 2,
+        )
+        .await;
+    assert!(res.is_err());
+
+    // Only the keeper gets the stop price
+    let res = spark
+        .fulfill_oco(
+            &bob,
+            &predicate,
+            alice.address(),
+            btc.asset_id,
+            base_amount,
+            usdc.asset_id,
+            stop_quote,
+        )
+        .await;
+    assert!(res.is_err());
+
+    spark
+        .fulfill_oco(
+            &carol,
+            &predicate,
+            alice.address(),
+            btc.asset_id,
+            base_amount,
+            usdc.asset_id,
+            stop_quote,
+        )
+        .await
+        .unwrap();
+
+    // The take profit went with it
+    let res = spark
+        .fulfill_oco(
+            &bob,
+            &predicate,
+            alice.address(),
+            btc.asset_id,
+            base_amount,
+            usdc.asset_id,
+            take_profit_quote,
+        )
+        .await;
+    assert!(res.is_err());
+
+    let predicate_balance = predicate.get_asset_balance(&btc.asset_id).await.unwrap();
+    let alice_usdc_balance = alice.get_asset_balance(&usdc.asset_id).await.unwrap();
+    let bob_btc_balance = bob.get_asset_balance(&btc.asset_id).await.unwrap();
+    let carol_btc_balance = carol.get_asset_balance(&btc.asset_id).await.unwrap();
+
+    assert_eq!(predicate_balance, 0);
+    assert_eq!(alice_usdc_balance, stop_quote);
+    assert_eq!(bob_btc_balance, 0);
+    assert_eq!(carol_btc_balance, base_amount);
+}
@@ -0,0 +1,179 @@
+use fuels::crypto::SecretKey;
+use fuels::prelude::{TxPolicies, BASE_ASSET_ID};
+use fuels::test_helpers::{launch_custom_provider_and_get_wallets, WalletsConfig};
+use fuels::{prelude::ViewOnlyAccount, types::Address};
+use spark_sdk::offline::{OfflineAction, OfflineError, OfflineFormat, OfflineOutcome, OfflineTx};
+use spark_sdk::order::OrderOptions;
+use spark_sdk::print_title;
+use spark_sdk::signer::{LocalSigner, Signer, SignerAccount, WatchOnlySigner};
+use spark_sdk::spark_utils::Spark;
+use src20_sdk::token_utils::{deploy_token_contract, Asset};
+
+// Neither Alice's nor Bob's key ever touches a provider: txs are built from their addresses,
+// carried around as files, signed and handed back for submission
+#[tokio::test]
+async fn offline_order_test() {
+    print_title("Offline Order Test");
+    //--------------- WALLETS ---------------
+    let config = WalletsConfig::new(Some(5), Some(1), Some(1_000_000_000));
+    let wallets = launch_custom_provider_and_get_wallets(config, None, None)
+        .await
+        .unwrap();
+    let admin = &wallets[0];
+    let provider = admin.try_provider().unwrap().clone();
+
+    let alice_signer = LocalSigner::new(SecretKey::random(&mut rand::thread_rng()));
+    let alice = SignerAccount::new(
+        WatchOnlySigner::new(alice_signer.address().clone()),
+        provider.clone(),
+    );
+    let alice_address = Address::from(alice.address());
+    let bob_signer = LocalSigner::new(SecretKey::random(&mut rand::thread_rng()));
+    let bob = SignerAccount::new(
+        WatchOnlySigner::new(bob_signer.address().clone()),
+        provider.clone(),
+    );
+    let bob_address = Address::from(bob.address());
+    for address in [alice.address(), bob.address()] {
+        admin
+            .transfer(
+                address,
+                100_000_000,
+                BASE_ASSET_ID,
+                TxPolicies::default().with_gas_price(1),
+            )
+            .await
+            .unwrap();
+    }
+
+    //--------------- TOKENS ---------------
+    let token_contract = deploy_token_contract(&admin).await;
+    let usdc = Asset::new(admin.clone(), token_contract.contract_id().into(), "USDC");
+    let btc = Asset::new(admin.clone(), token_contract.contract_id().into(), "BTC");
+
+    let quote_amount = usdc.parse_units(40_000_f64) as u64; //40k USDC
+    let base_amount = btc.parse_units(1_f64) as u64; // 1 BTC
+
+    let price_decimals = 9;
+
+    let exp = price_decimals + btc.decimals - usdc.decimals;
+    let price = (quote_amount as u128 * 10u128.pow(exp as u32) / base_amount as u128) as u64;
+
+    usdc.mint(alice_address, quote_amount).await.unwrap();
+    btc.mint(bob_address, base_amount).await.unwrap();
+
+    //--------------- PREDICATE ---------
+    let spark = Spark::deploy_proxy(admin, &btc, &usdc).await;
+    let buy_predicate = spark.get_buy_predicate(&alice, price, 1);
+    let root = buy_predicate.address();
+
+    //--------------- CREATE ---------
+    let tx = spark
+        .with_account(&alice)
+        .build_create_order(
+            root.into(),
+            usdc.asset_id,
+            quote_amount,
+            price,
+            &OrderOptions::default(),
+        )
+        .await
+        .unwrap();
+    let offline_tx = OfflineTx::new(
+        OfflineAction::CreateOrder,
+        tx,
+        provider.chain_id(),
+        spark.proxy.contract_id(),
+        alice.address(),
+        &buy_predicate,
+    );
+    assert!(offline_tx
+        .utxos
+        .iter()
+        .any(|utxo| utxo.owner == format!("0x{alice_address}") && utxo.amount == quote_amount));
+    assert!(matches!(
+        offline_tx.submit(&provider).await,
+        Err(OfflineError::NotSigned)
+    ));
+
+    let dir = std::env::temp_dir().join(format!("spark-offline-{}", rand::random::<u64>()));
+    std::fs::create_dir_all(&dir).unwrap();
+    let path = dir.join("create_order.json");
+    offline_tx.save(&path, OfflineFormat::Json).unwrap();
+
+    // offline side
+    let mut offline_tx = OfflineTx::load(&path).unwrap();
+    assert!(matches!(
+        offline_tx.sign(&bob_signer).await,
+        Err(OfflineError::WrongSigner { .. })
+    ));
+    offline_tx.sign(&alice_signer).await.unwrap();
+    offline_tx.save(&path, OfflineFormat::Json).unwrap();
+
+    // back online
+    let offline_tx = OfflineTx::load(&path).unwrap();
+    match offline_tx.submit(&provider).await.unwrap() {
+        OfflineOutcome::OrderCreated { events, .. } => {
+            assert_eq!(events.len(), 1);
+            assert_eq!(events[0].predicate_root, root.into());
+            assert_eq!(events[0].price, price);
+        }
+        outcome => panic!("unexpected outcome {outcome:?}"),
+    }
+    let predicate_usdc_balance = provider
+        .get_asset_balance(root, usdc.asset_id)
+        .await
+        .unwrap();
+    assert_eq!(predicate_usdc_balance, quote_amount);
+
+    //--------------- FILL ---------
+    // Bob fills through the same workflow, binary this time
+    let tx = spark
+        .build_fulfill_order(
+            &bob,
+            &buy_predicate,
+            alice.address(),
+            usdc.asset_id,
+            quote_amount,
+            btc.asset_id,
+            base_amount,
+            &OrderOptions::default(),
+        )
+        .await
+        .unwrap();
+    let mut offline_tx = OfflineTx::new(
+        OfflineAction::FulfillOrder,
+        tx,
+        provider.chain_id(),
+        spark.proxy.contract_id(),
+        bob.address(),
+        &buy_predicate,
+    );
+    assert!(offline_tx.utxos.iter().any(|utxo| utxo.predicate));
+    let path = dir.join("fulfill_order.bin");
+    offline_tx.save(&path, OfflineFormat::Binary).unwrap();
+    offline_tx = OfflineTx::load(&path).unwrap();
+    offline_tx.sign(&bob_signer).await.unwrap();
+
+    match offline_tx.submit(&provider).await.unwrap() {
+        OfflineOutcome::OrderFilled { transfers, .. } => {
+            assert!(transfers.iter().any(|t| t.to == alice_address
+                && t.asset_id == btc.asset_id
+                && t.amount == base_amount));
+            assert!(transfers.iter().any(|t| t.to == bob_address
+                && t.asset_id == usdc.asset_id
+                && t.amount == quote_amount));
+        }
+        outcome => panic!("unexpected outcome {outcome:?}"),
+    }
+
+    let alice_btc_balance = provider
+        .get_asset_balance(alice.address(), btc.asset_id)
+        .await
+        .unwrap();
+    let bob_usdc_balance = bob.get_asset_balance(&usdc.asset_id).await.unwrap();
+    assert_eq!(alice_btc_balance, base_amount);
+    assert_eq!(bob_usdc_balance, quote_amount);
+
+    std::fs::remove_dir_all(dir).unwrap();
+}
@@ -0,0 +1,96 @@
+use fuels::test_helpers::{launch_custom_provider_and_get_wallets, WalletsConfig};
+use fuels::{prelude::ViewOnlyAccount, types::Address};
+use spark_sdk::order::{Expiry, OrderFee, OrderOptions};
+use spark_sdk::print_title;
+use spark_sdk::spark_utils::{
+    expiry_script_hash, CreateOrderEvent, Spark, PREDICATE_VERSION, PRICE_DECIMALS,
+};
+use src20_sdk::token_utils::{deploy_token_contract, Asset};
+
+// An indexer has to be able to rebuild the predicate from the event alone and check it against
+// the logged root
+#[tokio::test]
+async fn predicate_params_test() {
+    print_title("Predicate Params Test");
+    //--------------- WALLETS ---------------
+    let config = WalletsConfig::new(Some(5), Some(1), Some(1_000_000_000));
+    let wallets = launch_custom_provider_and_get_wallets(config, None, None)
+        .await
+        .unwrap();
+    let admin = &wallets[0];
+    let alice = &wallets[1];
+    let alice_address = Address::from(alice.address());
+    let treasury_address = Address::from(wallets[2].address());
+    let vault_address = Address::from(wallets[3].address());
+
+    //--------------- TOKENS ---------------
+    let token_contract = deploy_token_contract(&admin).await;
+    let usdc = Asset::new(admin.clone(), token_contract.contract_id().into(), "USDC");
+    let btc = Asset::new(admin.clone(), token_contract.contract_id().into(), "BTC");
+
+    let quote_amount = usdc.parse_units(40_000_f64) as u64; //40k USDC
+    let base_amount = btc.parse_units(1_f64) as u64; // 1 BTC
+
+    let price_decimals = 9;
+
+    let exp = price_decimals + btc.decimals - usdc.decimals;
+    let price = (quote_amount as u128 * 10u128.pow(exp as u32) / base_amount as u128) as u64;
+
+    usdc.mint(alice_address, quote_amount).await.unwrap();
+    btc.mint(alice_address, base_amount).await.unwrap();
+
+    //--------------- PREDICATE ---------
+    let spark = Spark::deploy_proxy(admin, &btc, &usdc).await;
+    let reader = spark.reader();
+
+    //--------------- THE TEST ---------
+    let plain = OrderOptions::default();
+    let everything = OrderOptions::default()
+        .with_min_fulfill_amount(1_000)
+        .with_expiry(Expiry::BlockHeight(1_000))
+        .with_random_nonce()
+        .with_fee(OrderFee::new(treasury_address, 30))
+        .with_recipient(vault_address)
+        .with_all_or_none();
+
+    for options in [plain, everything] {
+        let buy_predicate = spark.get_buy_predicate_with_options(alice, price, &options);
+        let sell_predicate = spark.get_sell_predicate_with_options(alice, price, &options);
+
+        for (predicate, asset_id, amount) in [
+            (&buy_predicate, usdc.asset_id, quote_amount / 2),
+            (&sell_predicate, btc.asset_id, base_amount / 2),
+        ] {
+            let response = spark
+                .with_account(alice)
+                .create_order_with_options(
+                    predicate.address().into(),
+                    asset_id,
+                    amount,
+                    price,
+                    &options,
+                )
+                .await
+                .unwrap();
+            let events = response
+                .decode_logs_with_type::<CreateOrderEvent>()
+                .unwrap();
+            let event = &events[0];
+
+            assert_eq!(event.base_asset_decimals, btc.decimals as u32);
+            assert_eq!(event.quote_asset_decimals, usdc.decimals as u32);
+            assert_eq!(event.price_decimals, PRICE_DECIMALS);
+            assert_eq!(event.predicate_version, PREDICATE_VERSION);
+            assert_eq!(event.min_fulfill_amount, options.min_fulfill_amount);
+            assert_eq!(event.recipient, options.recipient.unwrap_or_default());
+            if options.expiry == Expiry::Never {
+                assert_eq!(event.expiry_script_hash.0, [0; 32]);
+            } else {
+                assert_eq!(event.expiry_script_hash, expiry_script_hash());
+            }
+
+            let rebuilt = reader.predicate_from_event(event).unwrap();
+            assert_eq!(rebuilt.address(), predicate.address());
+        }
+    }
+}
@@ -0,0 +1,370 @@
+use fuels::accounts::predicate::Predicate;
+use fuels::test_helpers::{launch_custom_provider_and_get_wallets, WalletsConfig};
+use fuels::types::Bytes;
+use fuels::{prelude::ViewOnlyAccount, types::Address};
+use spark_sdk::auction::DutchAuction;
+use spark_sdk::ladder::{LadderLevel, PriceLadder};
+use spark_sdk::oco::OcoOrder;
+use spark_sdk::order::{random_nonce, OrderFee, OrderOptions};
+use spark_sdk::print_title;
+use spark_sdk::spark_utils::{
+    auction_template, ladder_template, oco_template, predicate_template, OrderType, Spark,
+};
+use src20_sdk::token_utils::{deploy_token_contract, Asset};
+
+// The proxy only logs orders whose root it can rebuild from the create_order arguments
+#[tokio::test]
+async fn predicate_root_test() {
+    print_title("Predicate Root Test");
+    //--------------- WALLETS ---------------
+    let config = WalletsConfig::new(Some(5), Some(1), Some(1_000_000_000));
+    let wallets = launch_custom_provider_and_get_wallets(config, None, None)
+        .await
+        .unwrap();
+    let admin = &wallets[0];
+    let alice = &wallets[1];
+    let alice_address = Address::from(alice.address());
+    let bob = &wallets[2];
+    let bob_address = Address::from(bob.address());
+    let treasury_address = Address::from(wallets[3].address());
+
+    //--------------- TOKENS ---------------
+    let token_contract = deploy_token_contract(&admin).await;
+    let usdc = Asset::new(admin.clone(), token_contract.contract_id().into(), "USDC");
+    let btc = Asset::new(admin.clone(), token_contract.contract_id().into(), "BTC");
+
+    let quote_amount = usdc.parse_units(40_000_f64) as u64; //40k USDC
+    let base_amount = btc.parse_units(1_f64) as u64; // 1 BTC
+
+    let price_decimals = 9;
+
+    let exp = price_decimals + btc.decimals - usdc.decimals;
+    let price = (quote_amount as u128 * 10u128.pow(exp as u32) / base_amount as u128) as u64;
+
+    usdc.mint(alice_address, quote_amount).await.unwrap();
+    btc.mint(alice_address, base_amount).await.unwrap();
+    btc.mint(bob_address, base_amount).await.unwrap();
+
+    //--------------- PREDICATE ---------
+    let spark = Spark::deploy_proxy(admin, &btc, &usdc).await;
+    let options = OrderOptions::default()
+        .with_random_nonce()
+        .with_fee(OrderFee::new(treasury_address, 30));
+    let sell_predicate = spark.get_sell_predicate_with_options(alice, price, &options);
+    let buy_predicate = spark.get_buy_predicate_with_options(alice, price, &options);
+
+    //--------------- THE TEST ---------
+    // An address that isn't a predicate at all
+    let res = spark
+        .with_account(alice)
+        .create_order_with_options(
+            Address::from([7u8; 32]),
+            btc.asset_id,
+            base_amount,
+            price,
+            &options,
+        )
+        .await;
+    assert!(res.is_err());
+
+    // A real predicate, but the event would claim other terms than it has
+    let forged_terms = [
+        (price + 1, options.clone()),
+        (price, options.clone().with_min_fulfill_amount(2)),
+        (price, options.clone().with_all_or_none()),
+        (price, options.clone().with_recipient(bob_address)),
+        (
+            price,
+            options.clone().with_fee(OrderFee::new(treasury_address, 0)),
+        ),
+    ];
+    for (forged_price, forged_options) in forged_terms {
+        let res = spark
+            .with_account(alice)
+            .create_order_with_options(
+                sell_predicate.address().into(),
+                btc.asset_id,
+                base_amount,
+                forged_price,
+                &forged_options,
+            )
+            .await;
+        assert!(res.is_err());
+    }
+
+    // Bob can't log an order as if Alice's predicate were his
+    let res = spark
+        .with_account(bob)
+        .create_order_with_options(
+            sell_predicate.address().into(),
+            btc.asset_id,
+            base_amount,
+            price,
+            &options,
+        )
+        .await;
+    assert!(res.is_err());
+
+    // Paying base into the buy predicate would log it as a sell order
+    let res = spark
+        .with_account(alice)
+        .create_order_with_options(
+            buy_predicate.address().into(),
+            btc.asset_id,
+            base_amount,
+            price,
+            &options,
+        )
+        .await;
+    assert!(res.is_err());
+
+    // Nothing left the makers' wallets
+    assert_eq!(
+        alice.get_asset_balance(&btc.asset_id).await.unwrap(),
+        base_amount
+    );
+    assert_eq!(
+        bob.get_asset_balance(&btc.asset_id).await.unwrap(),
+        base_amount
+    );
+
+    // The honest orders go through
+    for (predicate, asset_id, amount) in [
+        (&sell_predicate, btc.asset_id, base_amount),
+        (&buy_predicate, usdc.asset_id, quote_amount),
+    ] {
+        spark
+            .with_account(alice)
+            .create_order_with_options(
+                predicate.address().into(),
+                asset_id,
+                amount,
+                price,
+                &options,
+            )
+            .await
+            .unwrap();
+    }
+    let sell_balance = sell_predicate
+        .get_asset_balance(&btc.asset_id)
+        .await
+        .unwrap();
+    let buy_balance = buy_predicate
+        .get_asset_balance(&usdc.asset_id)
+        .await
+        .unwrap();
+    assert_eq!(sell_balance, base_amount);
+    assert_eq!(buy_balance, quote_amount);
+
+    // The proxy hashes a template into the same root as fuel-tx, "FUEL" seed included
+    for order_type in [OrderType::BUY, OrderType::SELL] {
+        let (bytecode, _) = predicate_template(&order_type);
+        let root = spark
+            .proxy
+            .methods()
+            .template_root(order_type)
+            .simulate()
+            .await
+            .unwrap()
+            .value;
+        assert_eq!(root, Predicate::from_code(bytecode).address().into());
+    }
+
+    // The templates are set once, by the deployer
+    for account in [bob, admin] {
+        let (bytecode, offsets) = predicate_template(&OrderType::SELL);
+        let res = spark
+            .with_account(account)
+            .proxy
+            .methods()
+            .set_predicate_template(OrderType::SELL, Bytes(bytecode), offsets)
+            .call()
+            .await;
+        assert!(res.is_err());
+    }
+}
+
+// Auctions, OCO orders and ladders are checked against their own templates
+#[tokio::test]
+async fn other_predicate_roots_test() {
+    print_title("Other Predicate Roots Test");
+    //--------------- WALLETS ---------------
+    let config = WalletsConfig::new(Some(5), Some(1), Some(1_000_000_000));
+    let wallets = launch_custom_provider_and_get_wallets(config, None, None)
+        .await
+        .unwrap();
+    let admin = &wallets[0];
+    let alice = &wallets[1];
+    let alice_address = Address::from(alice.address());
+    let bob = &wallets[2];
+    let bob_address = Address::from(bob.address());
+    let provider = admin.try_provider().unwrap();
+
+    //--------------- TOKENS ---------------
+    let token_contract = deploy_token_contract(&admin).await;
+    let usdc = Asset::new(admin.clone(), token_contract.contract_id().into(), "USDC");
+    let btc = Asset::new(admin.clone(), token_contract.contract_id().into(), "BTC");
+
+    let base_amount = btc.parse_units(1_f64) as u64; // 1 BTC
+    let exp = 9 + btc.decimals - usdc.decimals;
+    let price_of = |usd: f64| {
+        (usdc.parse_units(usd) as u128 * 10u128.pow(exp as u32) / base_amount as u128) as u64
+    };
+
+    btc.mint(alice_address, 3 * base_amount).await.unwrap();
+    btc.mint(bob_address, base_amount).await.unwrap();
+
+    //--------------- PREDICATES ---------
+    let spark = Spark::deploy_proxy(admin, &btc, &usdc).await;
+    let height = provider.latest_block_height().await.unwrap();
+    let auction = DutchAuction::new(
+        price_of(40_000_f64),
+        price_of(30_000_f64),
+        height,
+        height + 100,
+    );
+    let oco = OcoOrder::new(price_of(50_000_f64), price_of(35_000_f64), bob_address);
+    let ladder = PriceLadder::new(vec![
+        LadderLevel::new(price_of(40_000_f64), base_amount / 2),
+        LadderLevel::new(price_of(41_000_f64), base_amount / 2),
+    ]);
+    let nonce = random_nonce();
+    let auction_predicate = spark.get_dutch_auction_predicate(alice, &auction, nonce);
+    let oco_predicate = spark.get_oco_predicate(alice, &oco, nonce);
+    let ladder_predicate = spark.get_ladder_predicate(alice, &ladder, nonce);
+
+    //--------------- THE TEST ---------
+    // Other terms, another nonce or another maker than the predicate was built with
+    let other_auction = DutchAuction::new(
+        auction.start_price,
+        auction.end_price - 1,
+        height,
+        height + 100,
+    );
+    let other_oco = OcoOrder::new(oco.take_profit_price, oco.stop_price, alice_address);
+    let other_ladder = PriceLadder::new(vec![LadderLevel::new(price_of(40_000_f64), base_amount)]);
+    for (account, auction, nonce) in [
+        (alice, &other_auction, nonce),
+        (alice, &auction, random_nonce()),
+        (bob, &auction, nonce),
+    ] {
+        let res = spark
+            .with_account(account)
+            .create_auction(
+                auction_predicate.address().into(),
+                btc.asset_id,
+                base_amount,
+                auction,
+                nonce,
+            )
+            .await;
+        assert!(res
+            .unwrap_err()
+            .to_string()
+            .contains("PredicateRootMismatch"));
+    }
+    for (account, oco, nonce) in [
+        (alice, &other_oco, nonce),
+        (alice, &oco, random_nonce()),
+        (bob, &oco, nonce),
+    ] {
+        let res = spark
+            .with_account(account)
+            .create_oco_order(
+                oco_predicate.address().into(),
+                btc.asset_id,
+                base_amount,
+                oco,
+                nonce,
+            )
+            .await;
+        assert!(res
+            .unwrap_err()
+            .to_string()
+            .contains("PredicateRootMismatch"));
+    }
+    for (account, ladder, nonce) in [
+        (alice, &other_ladder, nonce),
+        (alice, &ladder, random_nonce()),
+        (bob, &ladder, nonce),
+    ] {
+        let res = spark
+            .with_account(account)
+            .create_ladder_order(
+                ladder_predicate.address().into(),
+                btc.asset_id,
+                ladder,
+                nonce,
+            )
+            .await;
+        assert!(res
+            .unwrap_err()
+            .to_string()
+            .contains("PredicateRootMismatch"));
+    }
+    assert_eq!(
+        alice.get_asset_balance(&btc.asset_id).await.unwrap(),
+        3 * base_amount
+    );
+
+    // The honest orders go through
+    spark
+        .with_account(alice)
+        .create_auction(
+            auction_predicate.address().into(),
+            btc.asset_id,
+            base_amount,
+            &auction,
+            nonce,
+        )
+        .await
+        .unwrap();
+    spark
+        .with_account(alice)
+        .create_oco_order(
+            oco_predicate.address().into(),
+            btc.asset_id,
+            base_amount,
+            &oco,
+            nonce,
+        )
+        .await
+        .unwrap();
+    spark
+        .with_account(alice)
+        .create_ladder_order(
+            ladder_predicate.address().into(),
+            btc.asset_id,
+            &ladder,
+            nonce,
+        )
+        .await
+        .unwrap();
+    assert_eq!(alice.get_asset_balance(&btc.asset_id).await.unwrap(), 0);
+
+    // These templates are set once too
+    let (bytecode, offsets) = auction_template();
+    let res = spark
+        .proxy
+        .methods()
+        .set_auction_template(Bytes(bytecode), offsets)
+        .call()
+        .await;
+    assert!(res.is_err());
+    let (bytecode, offsets) = oco_template();
+    let res = spark
+        .proxy
+        .methods()
+        .set_oco_template(Bytes(bytecode), offsets)
+        .call()
+        .await;
+    assert!(res.is_err());
+    let (bytecode, offsets) = ladder_template();
+    let res = spark
+        .proxy
+        .methods()
+        .set_ladder_template(Bytes(bytecode), offsets)
+        .call()
+        .await;
+    assert!(res.is_err());
+}
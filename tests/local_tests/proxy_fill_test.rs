@@ -0,0 +1,166 @@
+use fuels::test_helpers::{launch_custom_provider_and_get_wallets, WalletsConfig};
+use fuels::{prelude::ViewOnlyAccount, types::Address};
+use spark_sdk::order::{Expiry, OrderOptions};
+use spark_sdk::print_title;
+use spark_sdk::spark_utils::{FillEvent, OrderType, Spark};
+use src20_sdk::token_utils::{deploy_token_contract, Asset};
+
+/*
+outputs of a fill routed through the proxy, the contract output comes after the predicate layout
+    Coin { to: Alice, amount: 50000000, asset_id: BTC }
+    Change { to: Bob, amount: 0, asset_id: BTC }
+    Coin { to: Bob, amount: 20000000000, asset_id: USDC }
+    Change { to: Predicate, amount: 0, asset_id: USDC }
+    Contract { input_index: 2 }
+ */
+#[tokio::test]
+async fn proxy_fill_test() {
+    print_title("Proxy Fill Test");
+    //--------------- WALLETS ---------------
+    let config = WalletsConfig::new(Some(5), Some(1), Some(1_000_000_000));
+    let wallets = launch_custom_provider_and_get_wallets(config, None, None)
+        .await
+        .unwrap();
+    let admin = &wallets[0];
+    let alice = &wallets[1];
+    let alice_address = Address::from(alice.address());
+    let bob = wallets[2].clone();
+    let bob_address = Address::from(bob.address());
+
+    //--------------- TOKENS ---------------
+    let token_contract = deploy_token_contract(&admin).await;
+    let usdc = Asset::new(admin.clone(), token_contract.contract_id().into(), "USDC");
+    let btc = Asset::new(admin.clone(), token_contract.contract_id().into(), "BTC");
+
+    let quote_amount = usdc.parse_units(40_000_f64) as u64; //40k USDC
+    let base_amount = btc.parse_units(1_f64) as u64; // 1 BTC
+
+    let price_decimals = 9;
+
+    let exp = price_decimals + btc.decimals - usdc.decimals;
+    let price = (quote_amount as u128 * 10u128.pow(exp as u32) / base_amount as u128) as u64;
+
+    usdc.mint(alice_address, quote_amount).await.unwrap();
+    btc.mint(alice_address, base_amount).await.unwrap();
+    usdc.mint(bob_address, quote_amount).await.unwrap();
+    btc.mint(bob_address, base_amount).await.unwrap();
+
+    //--------------- PREDICATE ---------
+    let spark = Spark::deploy_proxy(admin, &btc, &usdc).await;
+    let options = OrderOptions::default();
+    let buy_predicate = spark.get_buy_predicate_with_options(alice, price, &options);
+    let sell_predicate = spark.get_sell_predicate_with_options(alice, price, &options);
+
+    for (predicate, asset_id, amount) in [
+        (&buy_predicate, usdc.asset_id, quote_amount),
+        (&sell_predicate, btc.asset_id, base_amount),
+    ] {
+        spark
+            .with_account(alice)
+            .create_order_with_options(
+                predicate.address().into(),
+                asset_id,
+                amount,
+                price,
+                &options,
+            )
+            .await
+            .unwrap();
+    }
+
+    //--------------- THE TEST ---------
+    // Half of the buy order
+    let response = spark
+        .fulfill_order_via_proxy(
+            &bob,
+            &buy_predicate,
+            alice.address(),
+            usdc.asset_id,
+            quote_amount / 2,
+            btc.asset_id,
+            base_amount / 2,
+            &options,
+        )
+        .await
+        .unwrap();
+    assert_eq!(response.value, quote_amount / 2);
+
+    let events = response.decode_logs_with_type::<FillEvent>().unwrap();
+    assert_eq!(events.len(), 1);
+    assert_eq!(events[0].predicate_root, buy_predicate.address().into());
+    assert_eq!(events[0].taker, bob_address);
+    assert_eq!(events[0].order_type, OrderType::BUY);
+    assert_eq!(events[0].base_asset, btc.asset_id);
+    assert_eq!(events[0].quote_asset, usdc.asset_id);
+    assert_eq!(events[0].base_amount, base_amount / 2);
+    assert_eq!(events[0].quote_amount, quote_amount / 2);
+    assert_eq!(events[0].remaining_amount, quote_amount / 2);
+
+    // Indexers get the same events from the tx id alone
+    let reader = spark.reader();
+    let indexed = reader.fill_events(&response.tx_id.unwrap()).await.unwrap();
+    assert_eq!(indexed, events);
+
+    // The whole sell order
+    let response = spark
+        .fulfill_order_via_proxy(
+            &bob,
+            &sell_predicate,
+            alice.address(),
+            btc.asset_id,
+            base_amount,
+            usdc.asset_id,
+            quote_amount,
+            &options,
+        )
+        .await
+        .unwrap();
+    let events = reader
+        .decode_fill_events(response.receipts.as_slice())
+        .unwrap();
+    assert_eq!(events[0].order_type, OrderType::SELL);
+    assert_eq!(events[0].base_amount, base_amount);
+    assert_eq!(events[0].quote_amount, quote_amount);
+    assert_eq!(events[0].remaining_amount, 0);
+
+    // Without a predicate coin in the tx there's no fill to log
+    let forged = spark
+        .proxy
+        .methods()
+        .fulfill_order(spark.market_id(), buy_predicate.address().into())
+        .call()
+        .await;
+    assert!(forged.is_err());
+
+    // Expiring orders only take fills through the expiry script
+    let expiring = OrderOptions::default().with_expiry(Expiry::BlockHeight(1_000));
+    let res = spark
+        .fulfill_order_via_proxy(
+            &bob,
+            &buy_predicate,
+            alice.address(),
+            usdc.asset_id,
+            quote_amount / 2,
+            btc.asset_id,
+            base_amount / 2,
+            &expiring,
+        )
+        .await;
+    assert!(res.is_err());
+
+    let buy_balance = buy_predicate
+        .get_asset_balance(&usdc.asset_id)
+        .await
+        .unwrap();
+    let sell_balance = sell_predicate
+        .get_asset_balance(&btc.asset_id)
+        .await
+        .unwrap();
+    let alice_btc_balance = alice.get_asset_balance(&btc.asset_id).await.unwrap();
+    let alice_usdc_balance = alice.get_asset_balance(&usdc.asset_id).await.unwrap();
+
+    assert_eq!(buy_balance, quote_amount / 2);
+    assert_eq!(sell_balance, 0);
+    assert_eq!(alice_btc_balance, base_amount / 2);
+    assert_eq!(alice_usdc_balance, quote_amount);
+}
@@ -0,0 +1,137 @@
+use fuels::test_helpers::{launch_custom_provider_and_get_wallets, WalletsConfig};
+use fuels::{prelude::ViewOnlyAccount, types::Address};
+use primitive_types::U256;
+use spark_sdk::order::{OrderOptions, Price, PriceError};
+use spark_sdk::print_title;
+use spark_sdk::spark_utils::{CreateOrderEvent, Spark};
+use src20_sdk::token_utils::{deploy_token_contract, Asset};
+
+// Alice sells 1 BTC at 33,333.33... USDC, 1000/3 USDC units per satoshi, down to single sats
+#[tokio::test]
+async fn rational_price_test() {
+    print_title("Rational Price Test");
+    //--------------- WALLETS ---------------
+    let config = WalletsConfig::new(Some(5), Some(1), Some(1_000_000_000));
+    let wallets = launch_custom_provider_and_get_wallets(config, None, None)
+        .await
+        .unwrap();
+    let admin = &wallets[0];
+    let alice = &wallets[1];
+    let alice_address = Address::from(alice.address());
+    let bob = wallets[2].clone();
+    let bob_address = Address::from(bob.address());
+
+    //--------------- TOKENS ---------------
+    let token_contract = deploy_token_contract(&admin).await;
+    let usdc = Asset::new(admin.clone(), token_contract.contract_id().into(), "USDC");
+    let btc = Asset::new(admin.clone(), token_contract.contract_id().into(), "BTC");
+    let (base_decimals, quote_decimals) = (btc.decimals as u32, usdc.decimals as u32);
+
+    let base_amount = btc.parse_units(1_f64) as u64; // 1 BTC
+
+    btc.mint(alice_address, base_amount).await.unwrap();
+    usdc.mint(bob_address, usdc.parse_units(40_000_f64) as u64)
+        .await
+        .unwrap();
+
+    //--------------- PREDICATE ---------
+    let spark = Spark::deploy_proxy(admin, &btc, &usdc).await;
+    let price = Price::new(1_000, 3).unwrap();
+    let options = OrderOptions::default();
+    let sell_predicate = spark.get_sell_predicate_with_options(alice, price, &options);
+    let root = sell_predicate.address();
+
+    let response = spark
+        .with_account(alice)
+        .create_order_with_options(root.into(), btc.asset_id, base_amount, price, &options)
+        .await
+        .unwrap();
+    let events = response
+        .decode_logs_with_type::<CreateOrderEvent>()
+        .unwrap();
+    assert_eq!(events[0].price, 1_000);
+    assert_eq!(events[0].price_den, 3);
+
+    //--------------- THE TEST ---------
+    let fill = |base: u64, quote: u64| {
+        spark.fulfill_order(
+            &bob,
+            &sell_predicate,
+            alice.address(),
+            btc.asset_id,
+            base,
+            usdc.asset_id,
+            quote,
+        )
+    };
+
+    // A single satoshi costs 333.33 units, rounded up for Alice
+    let quote = price.quote_amount(1, base_decimals, quote_decimals);
+    assert_eq!(quote, 334);
+    fill(1, quote).await.unwrap();
+
+    // Short by one unit
+    assert!(fill(3, 999).await.is_err());
+    fill(3, 1_000).await.unwrap();
+
+    let rest = base_amount - 4;
+    let quote = price.quote_amount(rest, base_decimals, quote_decimals);
+    fill(rest, quote).await.unwrap();
+
+    let predicate_balance = sell_predicate
+        .get_asset_balance(&btc.asset_id)
+        .await
+        .unwrap();
+    let alice_usdc_balance = alice.get_asset_balance(&usdc.asset_id).await.unwrap();
+    let bob_btc_balance = bob.get_asset_balance(&btc.asset_id).await.unwrap();
+
+    assert_eq!(predicate_balance, 0);
+    assert_eq!(alice_usdc_balance, 334 + 1_000 + quote);
+    assert_eq!(bob_btc_balance, base_amount);
+
+    // A zero scaled price is rejected up front instead of dividing by zero
+    let zero = Price::from(0);
+    let buy_predicate = spark.get_buy_predicate_with_options(&bob, zero, &options);
+    let err = spark
+        .with_account(&bob)
+        .create_order_with_options(
+            buy_predicate.address().into(),
+            usdc.asset_id,
+            1_000,
+            zero,
+            &options,
+        )
+        .await
+        .unwrap_err();
+    assert!(err.to_string().contains("InvalidPrice"));
+}
+
+#[test]
+fn price_test() {
+    // 40k USDC (6 decimals) per BTC (8 decimals) both ways
+    let scaled = Price::from(40_000_000_000_000);
+    let rational = Price::new(400, 1).unwrap();
+    assert_eq!(
+        scaled.fraction(8, 6),
+        (
+            U256::from(40_000_000_000_000u64),
+            U256::from(100_000_000_000u64)
+        )
+    );
+    for price in [scaled, rational] {
+        assert_eq!(price.quote_amount(100_000_000, 8, 6), 40_000_000_000);
+        assert_eq!(price.base_amount(40_000_000_000, 8, 6), 100_000_000);
+    }
+
+    // Rounding goes the maker's way
+    let price = Price::new(1_000, 3).unwrap();
+    assert_eq!(price.quote_amount(1, 8, 6), 334);
+    assert_eq!(price.quote_amount(3, 8, 6), 1_000);
+    assert_eq!(price.base_amount(1, 8, 6), 1);
+    assert_eq!(price.base_amount(1_001, 8, 6), 4);
+
+    // Ratios can't be zero or divide by zero, no base amount buys at a zero scaled price
+    assert_eq!(Price::new(0, 3), Err(PriceError::ZeroNumerator));
+    assert_eq!(Price::new(3, 0), Err(PriceError::ZeroDenominator));
+    assert_eq!(Price::from(0).checked_base_amount(1_000, 8, 6), None);
+}
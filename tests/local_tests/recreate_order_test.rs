@@ -1,108 +1,96 @@
-use fuels::{
-    accounts::predicate::Predicate,
-    prelude::ViewOnlyAccount,
-    types::{Address, Bits256},
-};
-use spark_sdk::{
-    limit_orders_utils::{
-        limit_orders_interactions::{cancel_order, create_order},
-        BuyPredicateConfigurables,
-    },
-    proxy_utils::{deploy_proxy_contract, ProxySendFundsToPredicateParams},
-};
-
-use crate::utils::{
-    local_tests_utils::{init_tokens, init_wallets},
-    print_title,
-};
+use fuels::test_helpers::{launch_custom_provider_and_get_wallets, WalletsConfig};
+use fuels::{prelude::ViewOnlyAccount, types::Address};
+use spark_sdk::order::OrderOptions;
+use spark_sdk::print_title;
+use spark_sdk::spark_utils::{CreateOrderEvent, Spark};
+use src20_sdk::token_utils::{deploy_token_contract, Asset};
 
+// Alice places the same order twice, each one has to stay an order of its own
 #[tokio::test]
 async fn recreate_order_test() {
     print_title("Recreate Order Test");
     //--------------- WALLETS ---------------
-    let wallets = init_wallets().await;
+    let config = WalletsConfig::new(Some(5), Some(1), Some(1_000_000_000));
+    let wallets = launch_custom_provider_and_get_wallets(config, None, None)
+        .await
+        .unwrap();
     let admin = &wallets[0];
     let alice = &wallets[1];
     let alice_address = Address::from(alice.address());
 
-    println!("alice_address = 0x{:?}\n", alice_address);
     //--------------- TOKENS ---------------
-    let assets = init_tokens(&admin).await;
-    let usdc = assets.get("USDC").unwrap();
-    let usdc_instance = TokenContract::new(usdc.contract_id.into(), admin.clone());
-    let uni = assets.get("UNI").unwrap();
+    let token_contract = deploy_token_contract(&admin).await;
+    let usdc = Asset::new(admin.clone(), token_contract.contract_id().into(), "USDC");
+    let btc = Asset::new(admin.clone(), token_contract.contract_id().into(), "BTC");
 
-    let amount0 = 1000_000_000_u64; //1000 USDC
-    let amount1 = 200_000_000_000_u64; //200 UNI
-    println!("USDC AssetId (asset0) = 0x{:?}", usdc.asset_id);
-    println!("UNI AssetId (asset1) = 0x{:?}", uni.asset_id);
-    println!("amount0 = {:?} USDC", amount0 / 1000_000);
-    println!("amount1 = {:?} UNI", amount1 / 1000_000_000);
+    let quote_amount = usdc.parse_units(40_000_f64) as u64; //40k USDC
+    let base_amount = btc.parse_units(1_f64) as u64; // 1 BTC
 
     let price_decimals = 9;
-    let exp = (price_decimals + usdc.config.decimals - uni.config.decimals).into();
-    let price = amount1 * 10u64.pow(exp) / amount0;
-    println!("Price = {:?} UNI/USDC", price);
 
-    token_abi_calls::mint(&usdc_instance, amount0, alice_address)
-        .await
-        .unwrap();
-    let initial_alice_usdc_balance = alice.get_asset_balance(&usdc.asset_id).await.unwrap();
-    println!("Alice minting {:?} USDC\n", amount0 / 1000_000);
+    let exp = price_decimals + btc.decimals - usdc.decimals;
+    let price = (quote_amount as u128 * 10u128.pow(exp as u32) / base_amount as u128) as u64;
+
+    usdc.mint(alice_address, quote_amount).await.unwrap();
 
     //--------------- PREDICATE ---------
+    let spark = Spark::deploy_proxy(admin, &btc, &usdc).await;
+
+    // Without a nonce the same terms give the same root and the orders would merge
+    let first = spark.get_buy_predicate(alice, price, 1);
+    let second = spark.get_buy_predicate(alice, price, 1);
+    assert_eq!(first.address(), second.address());
 
-    let configurables = BuyPredicateConfigurables::new()
-        .set_ASSET0(Bits256::from_hex_str(&usdc.asset_id.to_string()).unwrap())
-        .set_ASSET1(Bits256::from_hex_str(&uni.asset_id.to_string()).unwrap())
-        .set_ASSET0_DECIMALS(usdc.config.decimals)
-        .set_ASSET1_DECIMALS(uni.config.decimals)
-        .set_MAKER(Bits256::from_hex_str(&alice.address().hash().to_string()).unwrap())
-        .set_PRICE(price);
+    let first_options = OrderOptions::default().with_random_nonce();
+    let second_options = OrderOptions::default().with_random_nonce();
+    assert_ne!(first_options.nonce, second_options.nonce);
+    let first = spark.get_buy_predicate_with_options(alice, price, &first_options);
+    let second = spark.get_buy_predicate_with_options(alice, price, &second_options);
+    assert_ne!(first.address(), second.address());
 
-    let predicate: Predicate =
-        Predicate::load_from("./predicate-buy/out/debug/predicate-buy.bin")
-            .unwrap()
-            .with_configurables(configurables)
-            .with_provider(admin.provider().unwrap().clone());
-    println!("Predicate root = {:?}\n", predicate.address());
     //--------------- THE TEST ---------
-    assert!(alice.get_asset_balance(&usdc.asset_id).await.unwrap() == amount0);
-    let proxy = deploy_proxy_contract(&alice, "proxy-contract/out/debug/proxy-contract.bin").await;
-    let params = ProxySendFundsToPredicateParams {
-        predicate_root: predicate.address().into(),
-        asset_0: usdc.contract_id.into(),
-        asset_1: uni.contract_id.into(),
-        maker: alice_address,
-        min_fulfill_amount_0: 1,
-        price,
-        asset_0_decimals: 6,
-        asset_1_decimals: 9,
-        price_decimals: 9,
-    };
-    let proxy_address = format!("0x{}", proxy.contract_id().hash);
-    println!("proxyAddress = {:?}", proxy_address);
-    create_order(&alice, &proxy_address, params.clone(), amount0 / 2)
-        .await
-        .unwrap();
-    println!("Alice transfers 500 USDC to predicate\n");
+    let mut events = vec![];
+    for (predicate, options) in [(&first, &first_options), (&second, &second_options)] {
+        let response = spark
+            .with_account(alice)
+            .create_order_with_options(
+                predicate.address().into(),
+                usdc.asset_id,
+                quote_amount / 2,
+                price,
+                options,
+            )
+            .await
+            .unwrap();
+        events.extend(
+            response
+                .decode_logs_with_type::<CreateOrderEvent>()
+                .unwrap(),
+        );
+    }
 
-    create_order(&alice, &proxy_address, params.clone(), amount0 / 2)
-        .await
-        .unwrap();
-    println!("Alice transfers 500 USDC to predicate\n");
+    assert_eq!(events.len(), 2);
+    assert_eq!(events[0].predicate_root, first.address().into());
+    assert_eq!(events[0].nonce, first_options.nonce);
+    assert_eq!(events[1].predicate_root, second.address().into());
+    assert_eq!(events[1].nonce, second_options.nonce);
+
+    // Balances stay apart
+    let first_balance = first.get_asset_balance(&usdc.asset_id).await.unwrap();
+    let second_balance = second.get_asset_balance(&usdc.asset_id).await.unwrap();
+    assert_eq!(first_balance, quote_amount / 2);
+    assert_eq!(second_balance, quote_amount / 2);
 
-    cancel_order(&alice, &predicate, usdc.asset_id, amount0)
+    // Cancelling one leaves the other on the book
+    spark
+        .cancel_order(alice, &first, usdc.asset_id, quote_amount / 2)
         .await
         .unwrap();
 
-    println!("Alice canceles the order\n");
-    // The predicate root's coin has been spent
-    let predicate_balance = predicate.get_asset_balance(&usdc.asset_id).await.unwrap();
-    assert_eq!(predicate_balance, 0);
-
-    // Wallet balance is the same as before it sent the coins to the predicate
-    let wallet_balance = alice.get_asset_balance(&usdc.asset_id).await.unwrap();
-    assert_eq!(wallet_balance, initial_alice_usdc_balance);
-    println!("Alice balance 1000 UDSC\n");
+    let first_balance = first.get_asset_balance(&usdc.asset_id).await.unwrap();
+    let second_balance = second.get_asset_balance(&usdc.asset_id).await.unwrap();
+    let alice_usdc_balance = alice.get_asset_balance(&usdc.asset_id).await.unwrap();
+    assert_eq!(first_balance, 0);
+    assert_eq!(second_balance, quote_amount / 2);
+    assert_eq!(alice_usdc_balance, quote_amount / 2);
 }
@@ -0,0 +1,198 @@
+use fuels::accounts::wallet::WalletUnlocked;
+use fuels::crypto::SecretKey;
+use fuels::prelude::{Account, TxPolicies, BASE_ASSET_ID};
+use fuels::test_helpers::{launch_custom_provider_and_get_wallets, WalletsConfig};
+use fuels::{prelude::ViewOnlyAccount, types::Address};
+use spark_sdk::order::Expiry;
+use spark_sdk::print_title;
+use spark_sdk::rfq::{RfqQuote, SignedRfqQuote};
+use spark_sdk::signer::LocalSigner;
+use spark_sdk::spark_utils::Spark;
+use src20_sdk::token_utils::{deploy_token_contract, Asset};
+
+// Alice funds an ask with 1 BTC once and quotes it to Bob off-chain
+#[tokio::test]
+async fn rfq_test() {
+    print_title("RFQ Test");
+    //--------------- WALLETS ---------------
+    let config = WalletsConfig::new(Some(5), Some(1), Some(1_000_000_000));
+    let wallets = launch_custom_provider_and_get_wallets(config, None, None)
+        .await
+        .unwrap();
+    let admin = &wallets[0];
+    let bob = wallets[2].clone();
+    let bob_address = Address::from(bob.address());
+    let provider = admin.try_provider().unwrap().clone();
+
+    let alice_key = SecretKey::random(&mut rand::thread_rng());
+    let alice = WalletUnlocked::new_from_private_key(alice_key, Some(provider.clone()));
+    let alice_signer = LocalSigner::new(alice_key);
+    let alice_address = Address::from(alice.address());
+    admin
+        .transfer(
+            alice.address(),
+            100_000_000,
+            BASE_ASSET_ID,
+            TxPolicies::default().with_gas_price(1),
+        )
+        .await
+        .unwrap();
+
+    //--------------- TOKENS ---------------
+    let token_contract = deploy_token_contract(&admin).await;
+    let usdc = Asset::new(admin.clone(), token_contract.contract_id().into(), "USDC");
+    let btc = Asset::new(admin.clone(), token_contract.contract_id().into(), "BTC");
+
+    let quote_amount = usdc.parse_units(40_000_f64) as u64; //40k USDC
+    let base_amount = btc.parse_units(1_f64) as u64; // 1 BTC
+
+    let price_decimals = 9;
+
+    let exp = price_decimals + btc.decimals - usdc.decimals;
+    let price = (quote_amount as u128 * 10u128.pow(exp as u32) / base_amount as u128) as u64;
+
+    btc.mint(alice_address, base_amount).await.unwrap();
+    usdc.mint(bob_address, quote_amount).await.unwrap();
+
+    //--------------- PREDICATE ---------
+    let spark = Spark::deploy_proxy(admin, &btc, &usdc).await;
+    let ask = spark.get_rfq_predicate(&alice, false);
+    alice
+        .transfer(
+            ask.address(),
+            base_amount,
+            btc.asset_id,
+            TxPolicies::default().with_gas_price(1),
+        )
+        .await
+        .unwrap();
+
+    let reader = spark.reader();
+    let coin = reader
+        .rfq_coin(ask.address(), btc.asset_id)
+        .await
+        .unwrap()
+        .unwrap();
+    let expiry = Expiry::BlockHeight(provider.latest_block_height().await.unwrap() + 50);
+    let quote = RfqQuote::new(coin, price, base_amount / 2, expiry, 1)
+        .sign(&alice_signer, &btc.asset_id, &usdc.asset_id, false)
+        .await
+        .unwrap();
+
+    //--------------- THE TEST ---------
+    let fill = {
+        let (spark, bob, ask, alice) = (&spark, &bob, &ask, &alice);
+        let (btc, usdc) = (btc.asset_id, usdc.asset_id);
+        move |signed_quote: SignedRfqQuote, base: u64, quote: u64| async move {
+            spark
+                .fulfill_rfq(
+                    bob,
+                    ask,
+                    alice.address(),
+                    &signed_quote,
+                    btc,
+                    base,
+                    usdc,
+                    quote,
+                )
+                .await
+        }
+    };
+
+    // Over the quoted size
+    assert!(fill(quote, base_amount, quote_amount).await.is_err());
+
+    // Not signed by Alice
+    let bob_signer = LocalSigner::new(SecretKey::random(&mut rand::thread_rng()));
+    let forged = quote
+        .quote
+        .sign(&bob_signer, &btc.asset_id, &usdc.asset_id, false)
+        .await
+        .unwrap();
+    assert!(fill(forged, base_amount / 2, quote_amount / 2)
+        .await
+        .is_err());
+
+    // A better price than the one signed
+    let tampered = SignedRfqQuote {
+        quote: RfqQuote {
+            price: price / 2,
+            ..quote.quote
+        },
+        signature: quote.signature,
+    };
+    assert!(fill(tampered, base_amount / 2, quote_amount / 4)
+        .await
+        .is_err());
+
+    // Alice's bids don't work on her ask
+    let bid = quote
+        .quote
+        .sign(&alice_signer, &btc.asset_id, &usdc.asset_id, true)
+        .await
+        .unwrap();
+    assert!(fill(bid, base_amount / 2, quote_amount / 2).await.is_err());
+
+    fill(quote, base_amount / 2, quote_amount / 2)
+        .await
+        .unwrap();
+
+    // The fill spent the quoted coin, the quote can't be used again
+    assert!(fill(quote, base_amount / 4, quote_amount / 4)
+        .await
+        .is_err());
+
+    // Quotes for the change coin, Alice moves it to take back the cheap one
+    let coin = reader
+        .rfq_coin(ask.address(), btc.asset_id)
+        .await
+        .unwrap()
+        .unwrap();
+    let stale_quote = RfqQuote::new(coin, price, base_amount / 2, expiry, 2)
+        .sign(&alice_signer, &btc.asset_id, &usdc.asset_id, false)
+        .await
+        .unwrap();
+    spark
+        .revoke_rfq_quotes(&alice, &ask, btc.asset_id)
+        .await
+        .unwrap();
+    assert!(fill(stale_quote, base_amount / 4, quote_amount / 4)
+        .await
+        .is_err());
+
+    let new_price = price / 40 * 42;
+    let sign_new_quote = |nonce| {
+        let (reader, ask, alice_signer) = (&reader, &ask, &alice_signer);
+        let (btc, usdc) = (btc.asset_id, usdc.asset_id);
+        async move {
+            let coin = reader.rfq_coin(ask.address(), btc).await.unwrap().unwrap();
+            RfqQuote::new(coin, new_price, base_amount / 2, expiry, nonce)
+                .sign(alice_signer, &btc, &usdc, false)
+                .await
+                .unwrap()
+        }
+    };
+    let paid = quote_amount / 4 / 40 * 42; // 10.5k USDC for 0.25 BTC
+    fill(sign_new_quote(3).await, base_amount / 4, paid)
+        .await
+        .unwrap();
+
+    let late_quote = sign_new_quote(4).await;
+    provider.produce_blocks(60, None).await.unwrap();
+    assert!(fill(late_quote, base_amount / 4, paid).await.is_err());
+
+    spark
+        .cancel_order(&alice, &ask, btc.asset_id, base_amount / 4)
+        .await
+        .unwrap();
+
+    let ask_balance = ask.get_asset_balance(&btc.asset_id).await.unwrap();
+    let alice_btc_balance = alice.get_asset_balance(&btc.asset_id).await.unwrap();
+    let alice_usdc_balance = alice.get_asset_balance(&usdc.asset_id).await.unwrap();
+    let bob_btc_balance = bob.get_asset_balance(&btc.asset_id).await.unwrap();
+
+    assert_eq!(ask_balance, 0);
+    assert_eq!(alice_btc_balance, base_amount / 4);
+    assert_eq!(alice_usdc_balance, quote_amount / 2 + paid);
+    assert_eq!(bob_btc_balance, base_amount * 3 / 4);
+}